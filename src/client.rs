@@ -1,11 +1,42 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use futures_timer::Delay;
 use reqwest::{
-    Client as ReqwestClient, ClientBuilder as ReqwestClientBuilder, Proxy, RequestBuilder,
+    header::{HeaderMap, ACCEPT_LANGUAGE},
+    Client as ReqwestClient, ClientBuilder as ReqwestClientBuilder, Proxy, RequestBuilder, Url,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::Error,
+    metrics,
+    rate_limiter::RateLimiter,
+    reference_data::ReferenceDataCache,
+    single_flight::SingleFlight,
+    transport::{Transport, TransportResponse},
+    util::ResponseUnion,
 };
 
-#[derive(Debug)]
 pub struct ClientBuilder {
     api_key: Option<String>,
     api_url: String,
+    accept_language: Option<String>,
+    rewrite_pagination_host: bool,
+    single_flight: bool,
+    reference_data_ttl: Option<Duration>,
+    timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    labeled_proxies: Vec<(String, Proxy)>,
+    labeled_http_clients: Vec<(String, ReqwestClient)>,
+    rate_limit: Option<(u32, Duration)>,
+    on_request: Option<RequestHook>,
+    transport: Option<Arc<dyn Transport>>,
+    http_client: Option<ReqwestClient>,
     reqwest_client_builder: ReqwestClientBuilder,
 }
 
@@ -15,6 +46,18 @@ impl ClientBuilder {
         ClientBuilder {
             api_key: None,
             api_url: "https://kodikapi.com".to_owned(),
+            accept_language: None,
+            rewrite_pagination_host: false,
+            single_flight: false,
+            reference_data_ttl: None,
+            timeout: None,
+            retry_policy: None,
+            labeled_proxies: Vec::new(),
+            labeled_http_clients: Vec::new(),
+            rate_limit: None,
+            on_request: None,
+            transport: None,
+            http_client: None,
             reqwest_client_builder: ReqwestClientBuilder::new(),
         }
     }
@@ -36,6 +79,8 @@ impl ClientBuilder {
     ///
     /// Default: `https://kodikapi.com`
     ///
+    /// May include a path, paths of the endpoints are appended to it. A trailing slash is ignored.
+    ///
     /// ```
     /// use kodik_api::ClientBuilder;
     ///
@@ -47,6 +92,153 @@ impl ClientBuilder {
         self
     }
 
+    /// Rewrite the host of the `next_page` links returned by the API to `api_url` before following them
+    ///
+    /// Default: `false`, the links are followed as returned, which is the canonical Kodik host. Useful behind a mirror or a caching proxy, so that the following pages go through it as well.
+    ///
+    /// ```
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// ClientBuilder::new()
+    ///   .api_url("https://koooooooooooooodik.com/api")
+    ///   .rewrite_pagination_host(true);
+    /// ```
+    pub fn rewrite_pagination_host(mut self, rewrite_pagination_host: bool) -> ClientBuilder {
+        self.rewrite_pagination_host = rewrite_pagination_host;
+        self
+    }
+
+    /// Value of the `Accept-Language` header sent with every request
+    ///
+    /// Default: the header is not sent. Kodik does not document localized responses, so the API may ignore it. Can be overridden per query with `with_accept_language`
+    ///
+    /// ```
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// ClientBuilder::new()
+    ///   .accept_language("en");
+    /// ```
+    pub fn accept_language(mut self, accept_language: impl Into<String>) -> ClientBuilder {
+        self.accept_language = Some(accept_language.into());
+        self
+    }
+
+    /// Coalesce identical concurrent requests into a single one
+    ///
+    /// Concurrent `execute` calls with the same parameters share one in-flight request and all of them receive its result. Unlike caching, a finished request is not reused. Streams are never coalesced.
+    ///
+    /// If the shared request fails, callers that joined it receive [`Error::SharedRequestError`].
+    ///
+    /// Default: `false`
+    ///
+    /// ```
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// ClientBuilder::new()
+    ///   .single_flight(true);
+    /// ```
+    pub fn single_flight(mut self, single_flight: bool) -> ClientBuilder {
+        self.single_flight = single_flight;
+        self
+    }
+
+    /// How long the snapshot returned by [`Client::reference_data`] is cached
+    ///
+    /// Default: not cached
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// ClientBuilder::new()
+    ///   .reference_data_ttl(Duration::from_secs(60 * 60));
+    /// ```
+    pub fn reference_data_ttl(mut self, ttl: Duration) -> ClientBuilder {
+        self.reference_data_ttl = Some(ttl);
+        self
+    }
+
+    /// Timeout of every request, including each page request of a stream
+    ///
+    /// A timed out request fails with [`Error::HttpError`]
+    ///
+    /// Default: no timeout
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// ClientBuilder::new()
+    ///   .timeout(Duration::from_secs(10));
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> ClientBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry failed requests, including each page request of a stream
+    ///
+    /// A retried page request of a stream is sent to the same `next_page` link, so the stream keeps its position.
+    ///
+    /// Default: requests are not retried
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kodik_api::{ClientBuilder, RetryPolicy};
+    ///
+    /// ClientBuilder::new()
+    ///   .retry(RetryPolicy::new(3, Duration::from_millis(500)));
+    /// ```
+    pub fn retry(mut self, retry_policy: RetryPolicy) -> ClientBuilder {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Limit the rate of requests to `max_requests` per `per`, including each page request of a stream and each retry
+    ///
+    /// Bursts of up to `max_requests` requests are allowed, after that requests wait for their turn. The limit is shared between clones of the client.
+    ///
+    /// Default: no limit
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// ClientBuilder::new()
+    ///   .rate_limit(2, Duration::from_secs(1));
+    /// ```
+    pub fn rate_limit(mut self, max_requests: u32, per: Duration) -> ClientBuilder {
+        self.rate_limit = Some((max_requests, per));
+        self
+    }
+
+    /// Hook called with the URL of every request right before it is sent, including retries
+    ///
+    /// The API key in the URL is replaced with `***`. All requests send their parameters in the query string, so the URL is the whole request.
+    ///
+    /// ```
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// ClientBuilder::new()
+    ///   .on_request(|url| println!("requesting {url}"));
+    /// ```
+    pub fn on_request(mut self, hook: impl Fn(&str) + Send + Sync + 'static) -> ClientBuilder {
+        self.on_request = Some(Arc::new(hook));
+        self
+    }
+
+    /// Send the requests through a custom transport instead of the reqwest client
+    ///
+    /// The requests are still built by the reqwest client, so the timeout, the proxies and the retries apply only as far as the transport respects them. Useful for testing code built on this crate, see `MockTransport` behind the `test-util` feature.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> ClientBuilder {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
     /// ```
     /// use kodik_api::ClientBuilder;
     ///
@@ -58,6 +250,46 @@ impl ClientBuilder {
         self
     }
 
+    /// Register a proxy which can be chosen per request with [`Client::via_proxy`]
+    ///
+    /// Proxies are bound to a reqwest client when it is built, so every labeled proxy gets its own reqwest client with default settings and they must all be registered up front. `custom_reqwest_builder` does not apply to them, use [`ClientBuilder::labeled_http_client`] to configure the client of a label.
+    ///
+    /// ```
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// ClientBuilder::new()
+    ///   .labeled_proxy("jp", reqwest::Proxy::all("https://jp.my.prox").unwrap());
+    /// ```
+    pub fn labeled_proxy(mut self, label: impl Into<String>, proxy: Proxy) -> ClientBuilder {
+        self.labeled_proxies.push((label.into(), proxy));
+        self
+    }
+
+    /// Register an already built reqwest client which can be chosen per request with [`Client::via_proxy`]
+    ///
+    /// Unlike [`ClientBuilder::labeled_proxy`], the client keeps all of its settings, such as timeouts, headers or TLS options. Takes precedence over a labeled proxy with the same label.
+    ///
+    /// ```
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// let jp_http_client = reqwest::Client::builder()
+    ///     .proxy(reqwest::Proxy::all("https://jp.my.prox").unwrap())
+    ///     .user_agent("my-app")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// ClientBuilder::new()
+    ///   .labeled_http_client("jp", jp_http_client);
+    /// ```
+    pub fn labeled_http_client(
+        mut self,
+        label: impl Into<String>,
+        http_client: ReqwestClient,
+    ) -> ClientBuilder {
+        self.labeled_http_clients.push((label.into(), http_client));
+        self
+    }
+
     /// ```
     /// use kodik_api::ClientBuilder;
     ///
@@ -69,6 +301,25 @@ impl ClientBuilder {
         self
     }
 
+    /// Use an already built reqwest client, so its connection pool is shared with the other code using it
+    ///
+    /// Takes precedence over [`ClientBuilder::custom_reqwest_builder`].
+    ///
+    /// ```
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// let http_client = reqwest::Client::new();
+    ///
+    /// ClientBuilder::new()
+    ///   .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+    ///   .http_client(http_client.clone())
+    ///   .build();
+    /// ```
+    pub fn http_client(mut self, http_client: ReqwestClient) -> ClientBuilder {
+        self.http_client = Some(http_client);
+        self
+    }
+
     // TODO: Add handle errors
     /// # Panic
     /// If api_key is not set and if it was not possible to build http client
@@ -82,10 +333,37 @@ impl ClientBuilder {
         Client {
             api_key: self.api_key.expect("api key is required"),
             api_url: self.api_url,
-            http_client: self
-                .reqwest_client_builder
-                .build()
-                .expect("failed to build reqwest client"),
+            accept_language: self.accept_language,
+            rewrite_pagination_host: self.rewrite_pagination_host,
+            single_flight: self.single_flight.then(SingleFlight::default),
+            reference_data_cache: self.reference_data_ttl.map(ReferenceDataCache::new),
+            timeout: self.timeout,
+            retry_policy: self.retry_policy,
+            rate_limiter: self
+                .rate_limit
+                .map(|(max_requests, per)| RateLimiter::new(max_requests, per)),
+            on_request: self.on_request,
+            transport: self.transport,
+            proxy_label: None,
+            proxy_http_clients: Arc::new(
+                self.labeled_proxies
+                    .into_iter()
+                    .map(|(label, proxy)| {
+                        let http_client = ReqwestClientBuilder::new()
+                            .proxy(proxy)
+                            .build()
+                            .expect("failed to build reqwest client");
+
+                        (label, http_client)
+                    })
+                    .chain(self.labeled_http_clients)
+                    .collect(),
+            ),
+            http_client: self.http_client.unwrap_or_else(|| {
+                self.reqwest_client_builder
+                    .build()
+                    .expect("failed to build reqwest client")
+            }),
         }
     }
 }
@@ -96,11 +374,226 @@ impl Default for ClientBuilder {
     }
 }
 
-/// The top-level struct of the SDK, representing a client
+// The API key is redacted, so that it doesn't leak into logs
+impl fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("api_key", &self.api_key.as_ref().map(|_| REDACTED))
+            .field("api_url", &self.api_url)
+            .field("accept_language", &self.accept_language)
+            .field("rewrite_pagination_host", &self.rewrite_pagination_host)
+            .field("single_flight", &self.single_flight)
+            .field("reference_data_ttl", &self.reference_data_ttl)
+            .field("timeout", &self.timeout)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limit", &self.rate_limit)
+            .field("on_request", &self.on_request.is_some())
+            .field("transport", &self.transport.is_some())
+            .field("http_client", &self.http_client.is_some())
+            .field(
+                "labeled_proxies",
+                &self
+                    .labeled_proxies
+                    .iter()
+                    .map(|(label, _)| label)
+                    .chain(self.labeled_http_clients.iter().map(|(label, _)| label))
+                    .collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+const REDACTED: &str = "***";
+
+type RequestHook = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Returns the URL with the value of the `token` parameter replaced with `***`
+pub(crate) fn redact_token(url: &Url) -> String {
+    let mut url = url.clone();
+
+    let query_pairs = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if key == "token" {
+                REDACTED.into()
+            } else {
+                value
+            };
+
+            (key.into_owned(), value.into_owned())
+        })
+        .collect::<Vec<_>>();
+
+    if !query_pairs.is_empty() {
+        url.query_pairs_mut().clear().extend_pairs(query_pairs);
+    }
+
+    url.to_string()
+}
+
+/// Joins the base URL and the path with exactly one slash between them
+fn join_url(base: &str, path: &str) -> String {
+    format!(
+        "{}/{}",
+        base.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    )
+}
+
+/// Maximum length of a response body kept in errors
+const MAX_ERROR_BODY_LEN: usize = 1024;
+
+pub(crate) fn truncate_body(mut body: String) -> String {
+    if body.len() > MAX_ERROR_BODY_LEN {
+        let mut len = MAX_ERROR_BODY_LEN;
+
+        while !body.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        body.truncate(len);
+        body.push('…');
+    }
+
+    body
+}
+
+/// Status and headers of a successful response, returned by the `execute_raw` methods of the queries
+///
+/// Allows reading headers the typed responses don't cover, such as `Retry-After` or quota hints.
 #[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    pub status: u16,
+
+    pub headers: HeaderMap,
+}
+
+/// Policy of retrying failed requests with exponential backoff
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_retry_after: Duration,
+    is_retryable: fn(&Error) -> bool,
+}
+
+impl RetryPolicy {
+    /// Create a policy which makes at most `max_attempts` attempts, including the first one
+    ///
+    /// The delay before the n-th retry is `base_delay * 2^(n - 1)`, unless the response requested another one with the `Retry-After` header, see [`RetryPolicy::max_retry_after`]. Only [`RetryPolicy::is_transient`] errors are retried.
+    pub fn new(max_attempts: u32, base_delay: Duration) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay,
+            max_retry_after: Duration::from_secs(60),
+            is_retryable: RetryPolicy::is_transient,
+        }
+    }
+
+    /// The longest delay taken from the `Retry-After` header, see [`Error::retry_after`]
+    ///
+    /// Longer delays requested by the API are cut down to it. A `429` with `Retry-After` is retried by [`RetryPolicy::is_transient`], to retry the ones without it and the rate limit messages of Kodik as well, use [`Error::is_retryable`].
+    ///
+    /// Default: 60 seconds
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kodik_api::{error::Error, RetryPolicy};
+    ///
+    /// RetryPolicy::new(3, Duration::from_millis(500))
+    ///   .max_retry_after(Duration::from_secs(10))
+    ///   .retryable(Error::is_retryable);
+    /// ```
+    pub fn max_retry_after(mut self, max_retry_after: Duration) -> RetryPolicy {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+
+    /// Which errors are retried
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use kodik_api::{error::Error, RetryPolicy};
+    ///
+    /// RetryPolicy::new(3, Duration::from_millis(500))
+    ///   .retryable(|err| matches!(err, Error::HttpError(err) if err.is_timeout()));
+    /// ```
+    pub fn retryable(mut self, is_retryable: fn(&Error) -> bool) -> RetryPolicy {
+        self.is_retryable = is_retryable;
+        self
+    }
+
+    /// Checks whether the error is a network error, a server error (5xx) or a rate limit (429) with the `Retry-After` header, which may go away on a retry
+    ///
+    /// [`Error::KodikError`] is never transient.
+    pub fn is_transient(err: &Error) -> bool {
+        match err {
+            Error::HttpError(err) => {
+                err.is_connect()
+                    || err.is_timeout()
+                    || err.is_request()
+                    || err.is_body()
+                    || err.status().is_some_and(|status| status.is_server_error())
+            }
+            Error::HttpStatus { status: 429, .. } => err.retry_after().is_some(),
+            Error::HttpStatus { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+
+    /// Delay before retrying the failed attempt, `Retry-After` of the response takes precedence over the backoff
+    fn retry_delay(&self, attempt: u32, err: &Error) -> Duration {
+        match err.retry_after() {
+            Some(retry_after) => retry_after.min(self.max_retry_after),
+            None => self.delay(attempt),
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(500))
+    }
+}
+
+/// How batch operations handle the failure of a single item
+///
+/// Used by [`Client::search_titles`] and [`Client::search_by_ids`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FailureMode {
+    /// Errors are yielded for the failed items and the others continue
+    #[default]
+    Collect,
+
+    /// The first error is yielded and the batch stops, outstanding requests are cancelled
+    FailFast,
+}
+
+/// The top-level struct of the SDK, representing a client
+///
+/// Cloning is cheap, and clones share the connection pool, the reference data cache, the rate limit and the in-flight requests of [`ClientBuilder::single_flight`], so a clone can be handed to every task. The reqwest client is reference counted itself, so cloning it doesn't open new connections.
+#[derive(Clone)]
 pub struct Client {
     api_key: String,
     api_url: String,
+    accept_language: Option<String>,
+    rewrite_pagination_host: bool,
+    single_flight: Option<SingleFlight>,
+    pub(crate) reference_data_cache: Option<ReferenceDataCache>,
+    timeout: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    on_request: Option<RequestHook>,
+    transport: Option<Arc<dyn Transport>>,
+    proxy_label: Option<String>,
+    proxy_http_clients: Arc<HashMap<String, ReqwestClient>>,
     http_client: ReqwestClient,
 }
 
@@ -120,13 +613,797 @@ impl Client {
         ClientBuilder::new().api_key(api_key).build()
     }
 
+    /// Create a client with an already built reqwest client, so its connection pool is shared with the other code using it
+    ///
+    /// Same as [`ClientBuilder::http_client`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kodik_api::Client;
+    ///
+    /// let http_client = reqwest::Client::new();
+    ///
+    /// let client = Client::with_http_client("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7", http_client.clone());
+    /// ```
+    pub fn with_http_client(api_key: impl Into<String>, http_client: ReqwestClient) -> Client {
+        ClientBuilder::new()
+            .api_key(api_key)
+            .http_client(http_client)
+            .build()
+    }
+
+    /// Create a client from environment variables
+    ///
+    /// Reads the following variables:
+    /// - `KODIK_API_KEY` - API key (token) for Kodik API, required
+    /// - `KODIK_API_URL` - Base URL for Kodik API, optional
+    ///
+    /// # Errors
+    /// Returns [`Error::MissingApiKey`] if `KODIK_API_KEY` is not set
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use kodik_api::Client;
+    ///
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    /// ```
+    pub fn from_env() -> Result<Client, Error> {
+        let api_key = std::env::var("KODIK_API_KEY").map_err(|_| Error::MissingApiKey)?;
+
+        let mut builder = ClientBuilder::new().api_key(api_key);
+
+        if let Ok(api_url) = std::env::var("KODIK_API_URL") {
+            builder = builder.api_url(api_url);
+        }
+
+        Ok(builder.build())
+    }
+
+    /// Returns a client which sends requests through the proxy registered with [`ClientBuilder::labeled_proxy`] or the reqwest client registered with [`ClientBuilder::labeled_http_client`]
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidParameter`] if no proxy is registered with the label
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kodik_api::ClientBuilder;
+    ///
+    /// let client = ClientBuilder::new()
+    ///     .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+    ///     .labeled_proxy("jp", reqwest::Proxy::all("https://jp.my.prox").unwrap())
+    ///     .build();
+    ///
+    /// let jp_client = client.via_proxy("jp").unwrap();
+    /// ```
+    pub fn via_proxy(&self, label: &str) -> Result<Client, Error> {
+        let http_client = self.proxy_http_clients.get(label).ok_or_else(|| {
+            Error::InvalidParameter(format!("no proxy is registered with label {label}"))
+        })?;
+
+        Ok(Client {
+            proxy_label: Some(label.to_owned()),
+            http_client: http_client.clone(),
+            ..self.clone()
+        })
+    }
+
     pub(crate) fn init_post_request(&self, path_or_url: &str) -> RequestBuilder {
-        if !path_or_url.starts_with("http") {
+        self.init_localized_post_request(path_or_url, None)
+    }
+
+    /// Same as `init_post_request`, but `accept_language` takes precedence over the client one
+    pub(crate) fn init_localized_post_request(
+        &self,
+        path_or_url: &str,
+        accept_language: Option<&str>,
+    ) -> RequestBuilder {
+        let request_builder = if !path_or_url.starts_with("http") {
             self.http_client
-                .post(self.api_url.clone() + path_or_url)
+                .post(join_url(&self.api_url, path_or_url))
                 .query(&[("token", &self.api_key)])
+        } else if self.rewrite_pagination_host {
+            self.http_client.post(self.rewrite_host(path_or_url))
         } else {
             self.http_client.post(path_or_url.to_owned())
+        };
+
+        let request_builder = match self.timeout {
+            Some(timeout) => request_builder.timeout(timeout),
+            None => request_builder,
+        };
+
+        match accept_language.or(self.accept_language.as_deref()) {
+            Some(accept_language) => request_builder.header(ACCEPT_LANGUAGE, accept_language),
+            None => request_builder,
+        }
+    }
+
+    /// Replaces the scheme, the host and the port of the URL with `api_url`, keeping the path and the query
+    fn rewrite_host(&self, url: &str) -> String {
+        let Ok(url) = Url::parse(url) else {
+            return url.to_owned();
+        };
+
+        let mut rewritten_url = join_url(&self.api_url, url.path());
+
+        if let Some(query) = url.query() {
+            rewritten_url.push('?');
+            rewritten_url.push_str(query);
+        }
+
+        rewritten_url
+    }
+
+    /// Sends the request and decodes the JSON response, retrying it according to [`ClientBuilder::retry`]
+    pub(crate) async fn send_request<T: DeserializeOwned + ResponseUnion>(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> Result<T, Error> {
+        self.send_request_with_headers(request_builder)
+            .await
+            .map(|(_, result)| result)
+    }
+
+    /// Same as `send_request`, but also returns the status and the headers of the response
+    pub(crate) async fn send_request_with_headers<T: DeserializeOwned + ResponseUnion>(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> Result<(ResponseMeta, T), Error> {
+        let Some(retry_policy) = &self.retry_policy else {
+            return self.send_request_once(request_builder).await;
+        };
+
+        let mut attempt = 1;
+
+        loop {
+            // Requests with a streaming body can't be cloned and therefore can't be retried
+            let Some(attempt_request_builder) = request_builder.try_clone() else {
+                return self.send_request_once(request_builder).await;
+            };
+
+            match self.send_request_once(attempt_request_builder).await {
+                Err(err)
+                    if attempt < retry_policy.max_attempts && (retry_policy.is_retryable)(&err) =>
+                {
+                    Delay::new(retry_policy.retry_delay(attempt, &err)).await;
+
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send_request_once<T: DeserializeOwned + ResponseUnion>(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> Result<(ResponseMeta, T), Error> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+
+        let (http_client, request) = request_builder.build_split();
+
+        let request = request.map_err(Error::HttpError)?;
+
+        if let Some(on_request) = &self.on_request {
+            on_request(&redact_token(request.url()));
+        }
+
+        let endpoint = request.url().path().to_owned();
+
+        let transport: &dyn Transport = match &self.transport {
+            Some(transport) => transport.as_ref(),
+            None => &http_client,
+        };
+
+        let started_at = Instant::now();
+
+        let response = match transport.send(request).await {
+            Ok(response) => response,
+            Err(err) => {
+                metrics::record_request(&endpoint, "http_error", started_at.elapsed());
+
+                return Err(err);
+            }
+        };
+
+        if !response.status.is_success() {
+            metrics::record_request(&endpoint, "http_error", started_at.elapsed());
+
+            return Err(Error::HttpStatus {
+                status: response.status.as_u16(),
+                body: truncate_body(response.body),
+                headers: response.headers,
+            });
+        }
+
+        let TransportResponse {
+            status,
+            headers,
+            body,
+        } = response;
+
+        let result = serde_json::from_str::<T>(&body);
+
+        let outcome = match &result {
+            Ok(result) if result.is_error() => "kodik_error",
+            Ok(_) => "success",
+            Err(_) => "decode_error",
+        };
+
+        metrics::record_request(&endpoint, outcome, started_at.elapsed());
+
+        result
+            .map(|result| {
+                let meta = ResponseMeta {
+                    status: status.as_u16(),
+                    headers,
+                };
+
+                (meta, result)
+            })
+            .map_err(|source| Error::DecodeError {
+                source,
+                body: truncate_body(body),
+            })
+    }
+
+    /// Same as `send_request`, but identical concurrent requests are coalesced if [`ClientBuilder::single_flight`] is enabled
+    pub(crate) async fn execute_request<T>(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> Result<T, Error>
+    where
+        T: DeserializeOwned + ResponseUnion + Clone + Send + Sync + 'static,
+    {
+        let Some(single_flight) = &self.single_flight else {
+            return self.send_request(request_builder).await;
+        };
+
+        let key = request_builder
+            .try_clone()
+            .and_then(|request_builder| request_builder.build().ok())
+            .map(|request| {
+                format!(
+                    "{} {} {:?} {:?} {}",
+                    request.method(),
+                    request.url(),
+                    request.headers().get(ACCEPT_LANGUAGE),
+                    self.proxy_label,
+                    std::any::type_name::<T>()
+                )
+            });
+
+        let Some(key) = key else {
+            return self.send_request(request_builder).await;
+        };
+
+        let client = self.clone();
+
+        single_flight
+            .run(
+                key,
+                async move { client.send_request(request_builder).await },
+            )
+            .await
+    }
+}
+
+// The API key is redacted, so that it doesn't leak into logs
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("api_key", &REDACTED)
+            .field("api_url", &self.api_url)
+            .field("accept_language", &self.accept_language)
+            .field("rewrite_pagination_host", &self.rewrite_pagination_host)
+            .field("single_flight", &self.single_flight)
+            .field("reference_data_cache", &self.reference_data_cache)
+            .field("timeout", &self.timeout)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("on_request", &self.on_request.is_some())
+            .field("transport", &self.transport.is_some())
+            .field("proxy_label", &self.proxy_label)
+            .field(
+                "labeled_proxies",
+                &self.proxy_http_clients.keys().collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use crate::search::SearchQuery;
+
+    use super::*;
+
+    #[test]
+    fn test_accept_language() {
+        let client = Client::new("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7");
+
+        let request = client.init_post_request("/search").build().unwrap();
+
+        assert_eq!(request.headers().get(ACCEPT_LANGUAGE), None);
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .accept_language("ru")
+            .build();
+
+        let request = client.init_post_request("/search").build().unwrap();
+
+        assert_eq!(request.headers().get(ACCEPT_LANGUAGE).unwrap(), "ru");
+
+        let request = client
+            .init_localized_post_request("/search", Some("en"))
+            .build()
+            .unwrap();
+
+        assert_eq!(request.headers().get_all(ACCEPT_LANGUAGE).iter().count(), 1);
+        assert_eq!(request.headers().get(ACCEPT_LANGUAGE).unwrap(), "en");
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": "3ms", "total": 0, "results": [] }))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .timeout(Duration::from_millis(100))
+            .build();
+
+        let result = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .execute(&client)
+            .await;
+
+        assert!(matches!(result, Err(Error::HttpError(err)) if err.is_timeout()));
+    }
+
+    #[tokio::test]
+    async fn test_shared_across_tasks() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": "3ms", "total": 0, "results": [] })),
+            )
+            .expect(8)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .http_client(ReqwestClient::new())
+            .build();
+
+        let handles = (0..8)
+            .map(|_| {
+                let client = client.clone();
+
+                tokio::spawn(async move {
+                    SearchQuery::new()
+                        .with_title("Cyberpunk: Edgerunners")
+                        .execute(&client)
+                        .await
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            assert_eq!(handle.await.unwrap().unwrap().total, 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_status() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(429).set_body_string("Too Many Requests"))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(
+                ResponseTemplate::new(500).set_body_string("<html>Internal Server Error</html>"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let mut query = SearchQuery::new();
+
+        query.with_title("Cyberpunk: Edgerunners");
+
+        let err = query.execute(&client).await.unwrap_err();
+
+        assert!(
+            matches!(&err, Error::HttpStatus { status: 429, body, .. } if body == "Too Many Requests")
+        );
+        assert!(!RetryPolicy::is_transient(&err));
+
+        let err = query.execute(&client).await.unwrap_err();
+
+        assert!(matches!(
+            &err,
+            Error::HttpStatus { status: 500, body, .. } if body == "<html>Internal Server Error</html>"
+        ));
+        assert!(RetryPolicy::is_transient(&err));
+    }
+
+    #[tokio::test]
+    async fn test_decode_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"time":"3ms","total":"#))
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let err = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .execute(&client)
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(&err, Error::DecodeError { body, .. } if body == r#"{"time":"3ms","total":"#)
+        );
+    }
+
+    #[test]
+    fn test_join_url() {
+        for base in ["https://kodikapi.com", "https://kodikapi.com/"] {
+            for path in ["/list", "list"] {
+                assert_eq!(join_url(base, path), "https://kodikapi.com/list");
+            }
+        }
+
+        for base in ["https://mirror.com/api", "https://mirror.com/api/"] {
+            for path in ["/list", "list"] {
+                assert_eq!(join_url(base, path), "https://mirror.com/api/list");
+            }
+        }
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url("https://mirror.com/api/")
+            .build();
+
+        let request = client.init_post_request("/search").build().unwrap();
+
+        assert_eq!(request.url().path(), "/api/search");
+    }
+
+    #[test]
+    fn test_truncate_body() {
+        assert_eq!(truncate_body("short".to_owned()), "short");
+
+        let body = truncate_body("я".repeat(MAX_ERROR_BODY_LEN));
+
+        assert!(body.len() <= MAX_ERROR_BODY_LEN + '…'.len_utf8());
+        assert!(body.ends_with('…'));
+    }
+
+    #[tokio::test]
+    async fn test_retry_skips_kodik_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    serde_json::json!({ "error": "Отсутствует или неверный токен" }),
+                ),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .retry(RetryPolicy::new(3, Duration::from_millis(1)))
+            .build();
+
+        let result = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .execute(&client)
+            .await;
+
+        assert!(matches!(result, Err(Error::KodikError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "2"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "time": "3ms",
+                "total": 0,
+                "results": [],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // The backoff alone would wait for a minute
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .retry(
+                RetryPolicy::new(2, Duration::from_secs(60))
+                    .max_retry_after(Duration::from_millis(100))
+                    .retryable(Error::is_retryable),
+            )
+            .build();
+
+        let started_at = Instant::now();
+
+        let response = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .execute(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(response.total, 0);
+        assert!(started_at.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_default_policy() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "time": "3ms",
+                "total": 0,
+                "results": [],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // The backoff alone would wait for a minute
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .retry(RetryPolicy::new(2, Duration::from_secs(60)))
+            .build();
+
+        let started_at = Instant::now();
+
+        let response = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .execute(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(response.total, 0);
+        assert!(started_at.elapsed() >= Duration::from_secs(1));
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_on_request() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "time": "3ms",
+                "total": 0,
+                "results": [],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let urls = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .on_request({
+                let urls = urls.clone();
+
+                move |url| urls.lock().unwrap().push(url.to_owned())
+            })
+            .build();
+
+        SearchQuery::new()
+            .with_title("Cyberpunk")
+            .with_limit(1)
+            .execute(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *urls.lock().unwrap(),
+            [format!(
+                "{}/search?token=***&title=Cyberpunk&limit=1",
+                mock_server.uri()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let retry_policy = RetryPolicy::new(5, Duration::from_millis(100));
+
+        assert_eq!(retry_policy.delay(1), Duration::from_millis(100));
+        assert_eq!(retry_policy.delay(2), Duration::from_millis(200));
+        assert_eq!(retry_policy.delay(4), Duration::from_millis(800));
+
+        let mut headers = HeaderMap::new();
+
+        headers.insert("retry-after", "2".parse().unwrap());
+
+        let err = Error::HttpStatus {
+            status: 429,
+            body: String::new(),
+            headers,
+        };
+
+        assert_eq!(retry_policy.retry_delay(1, &err), Duration::from_secs(2));
+        assert_eq!(
+            retry_policy
+                .clone()
+                .max_retry_after(Duration::from_secs(1))
+                .retry_delay(1, &err),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            retry_policy.retry_delay(3, &Error::MissingApiKey),
+            Duration::from_millis(400)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_via_proxy() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": "3ms", "total": 0, "results": [] })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url("http://kodik-api.invalid")
+            .labeled_proxy("jp", Proxy::http(mock_server.uri()).unwrap())
+            .build();
+
+        let query = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .clone();
+
+        let response = query.execute_via(&client, "jp").await.unwrap();
+
+        assert_eq!(response.total, 0);
+
+        let result = query.execute_via(&client, "kz").await;
+
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[tokio::test]
+    async fn test_via_labeled_http_client() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(header("user-agent", "kodik-test"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": "3ms", "total": 0, "results": [] })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let jp_http_client = ReqwestClientBuilder::new()
+            .proxy(Proxy::http(mock_server.uri()).unwrap())
+            .user_agent("kodik-test")
+            .build()
+            .unwrap();
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url("http://kodik-api.invalid")
+            .labeled_http_client("jp", jp_http_client)
+            .build();
+
+        let response = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .execute_via(&client, "jp")
+            .await
+            .unwrap();
+
+        assert_eq!(response.total, 0);
+    }
+
+    #[test]
+    fn test_debug_redacts_api_key() {
+        let client_builder = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .labeled_proxy("jp", Proxy::all("https://jp.my.prox").unwrap());
+
+        let output = format!("{client_builder:?}");
+
+        assert!(!output.contains("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7"));
+        assert!(output.contains("https://kodikapi.com"));
+        assert!(output.contains("jp"));
+
+        let client = client_builder.build();
+
+        for output in [format!("{client:?}"), format!("{client:#?}")] {
+            assert!(!output.contains("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7"));
+            assert!(output.contains("***"));
+            assert!(output.contains("https://kodikapi.com"));
         }
     }
 }
@@ -1,12 +1,17 @@
+use std::{borrow::Cow, fmt, str::FromStr};
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
     error::Error,
     types::{
-        AllStatus, AnimeKind, AnimeStatus, DramaStatus, MaterialDataField, MppaRating, ReleaseType,
-        TranslationType,
+        AllStatus, AnimeKind, AnimeStatus, Category, DramaStatus, IntoQueryList, MaterialDataField,
+        MppaRating, ProcessingTime, ReleaseType, TranslationType, Year,
+    },
+    util::{
+        deserialize_number_or_string, reject_commas_in_lists, serialize_into_query_parts,
+        ResponseUnion,
     },
-    util::serialize_into_query_parts,
     Client,
 };
 
@@ -16,19 +21,27 @@ pub struct CountryResult {
     pub title: String,
 
     /// The number of materials with this voice acting
+    #[serde(deserialize_with = "deserialize_number_or_string")]
     pub count: i32,
 }
 
 /// A struct containing countries results and other information about the countries
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CountryResponse {
     pub time: String,
+    #[serde(deserialize_with = "deserialize_number_or_string")]
     pub total: i32,
     pub prev_page: Option<String>,
     pub next_page: Option<String>,
     pub results: Vec<CountryResult>,
 }
 
+impl ProcessingTime for CountryResponse {
+    fn time(&self) -> &str {
+        &self.time
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(untagged)]
 enum CountryResponseUnion {
@@ -36,14 +49,50 @@ enum CountryResponseUnion {
     Error { error: String },
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl ResponseUnion for CountryResponseUnion {
+    fn is_error(&self) -> bool {
+        matches!(self, CountryResponseUnion::Error { .. })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum CountrySort {
+    #[default]
     #[serde(rename = "title")]
     Title,
     #[serde(rename = "count")]
     Count,
 }
 
+impl CountrySort {
+    /// Returns the value as it is sent to the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CountrySort::Title => "title",
+            CountrySort::Count => "count",
+        }
+    }
+}
+
+impl FromStr for CountrySort {
+    type Err = Error;
+
+    /// Parses the value as it is sent to the API, for example `"title"`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "title" => Ok(CountrySort::Title),
+            "count" => Ok(CountrySort::Count),
+            _ => Err(Error::InvalidParameter(format!("invalid sort {value:?}"))),
+        }
+    }
+}
+
+impl fmt::Display for CountrySort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct CountryQuery<'a> {
     /// What field to sort materials by
@@ -52,117 +101,121 @@ pub struct CountryQuery<'a> {
 
     /// Maximum number of outputs
     #[serde(skip_serializing_if = "Option::is_none")]
-    types: Option<&'a [ReleaseType]>,
+    types: Option<Cow<'a, [ReleaseType]>>,
 
     ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
     #[serde(skip_serializing_if = "Option::is_none")]
-    year: Option<&'a [u32]>,
+    year: Option<Cow<'a, [Year]>>,
 
     /// Filtering materials by translation ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    translation_id: Option<&'a [u32]>,
+    translation_id: Option<Cow<'a, [u32]>>,
     /// Filter content by translation type. Allows you to output only voice translation or only subtitles
     #[serde(skip_serializing_if = "Option::is_none")]
-    translation_type: Option<&'a [TranslationType]>,
+    translation_type: Option<Cow<'a, [TranslationType]>>,
 
     /// Filtering materials based on the presence of a specific field. Materials that have at least one of the listed fields are shown. In order to show only materials that have all the listed fields
     #[serde(skip_serializing_if = "Option::is_none")]
-    has_field: Option<&'a [MaterialDataField]>,
+    has_field: Option<Cow<'a, [MaterialDataField]>>,
     /// Filtering materials based on the presence of a specific field. Materials that have all the listed fields are shown
     #[serde(skip_serializing_if = "Option::is_none")]
-    has_field_and: Option<&'a [MaterialDataField]>,
+    has_field_and: Option<Cow<'a, [MaterialDataField]>>,
 
     /// Filtering materials by country. You can specify a single value or multiple values, separated by commas (then materials with at least one of the listed countries will be displayed). The parameter is case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    countries: Option<&'a [&'a str]>,
+    countries: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    genres: Option<&'a [&'a str]>,
+    genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_genres: Option<&'a [&'a str]>,
+    anime_genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    drama_genres: Option<&'a [&'a str]>,
+    drama_genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    all_genres: Option<&'a [&'a str]>,
+    all_genres: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by duration (in minutes). You can specify either a single value to search for the exact duration, or an interval.
     #[serde(skip_serializing_if = "Option::is_none")]
-    duration: Option<&'a [&'a str]>,
+    duration: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    kinopoisk_rating: Option<&'a [&'a str]>,
+    kinopoisk_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    imdb_rating: Option<&'a [&'a str]>,
+    imdb_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    shikimori_rating: Option<&'a [&'a str]>,
+    shikimori_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    mydramalist_rating: Option<&'a [&'a str]>,
+    mydramalist_rating: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    actors: Option<&'a [&'a str]>,
+    actors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    directors: Option<&'a [&'a str]>,
+    directors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    producers: Option<&'a [&'a str]>,
+    producers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    writers: Option<&'a [&'a str]>,
+    writers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    composers: Option<&'a [&'a str]>,
+    composers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    editors: Option<&'a [&'a str]>,
+    editors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    designers: Option<&'a [&'a str]>,
+    designers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    operators: Option<&'a [&'a str]>,
+    operators: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by age rating. You can specify a single value or multiple values, separated by commas. The parameter is case-insensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    rating_mpaa: Option<&'a [MppaRating]>,
+    rating_mpaa: Option<Cow<'a, [MppaRating]>>,
 
     /// Filter content by the minimum age from which it can be viewed. You can specify either a single value or a range of values
     #[serde(skip_serializing_if = "Option::is_none")]
-    minimal_age: Option<&'a [&'a str]>,
+    minimal_age: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by anime type. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_kind: Option<&'a [AnimeKind]>,
+    anime_kind: Option<Cow<'a, [AnimeKind]>>,
 
     /// Filters materials by MyDramaList tags. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    mydramalist_tags: Option<&'a [&'a str]>,
+    mydramalist_tags: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_status: Option<&'a [AnimeStatus]>,
+    anime_status: Option<Cow<'a, [AnimeStatus]>>,
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    drama_status: Option<&'a [DramaStatus]>,
+    drama_status: Option<Cow<'a, [DramaStatus]>>,
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    all_status: Option<&'a [AllStatus]>,
+    all_status: Option<Cow<'a, [AllStatus]>>,
 
     /// Filtering materials by anime studio. You can specify either one value or several values separated by commas (then materials with at least one of the listed studios will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_studios: Option<&'a [&'a str]>,
+    anime_studios: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by license owner. You can specify a single value or several values separated by commas (then materials that have at least one of the listed owners will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_licensed_by: Option<&'a [&'a str]>,
+    anime_licensed_by: Option<Cow<'a, [Cow<'a, str>]>>,
+
+    /// Client bound by [`Client::countries`], used by [`CountryQuery::send`]
+    #[serde(skip)]
+    client: Option<Client>,
 }
 
 impl<'a> CountryQuery<'a> {
@@ -202,271 +255,325 @@ impl<'a> CountryQuery<'a> {
             all_status: None,
             anime_studios: None,
             anime_licensed_by: None,
+            client: None,
         }
     }
 
     /// Maximum number of outputs
-    pub fn with_types<'b>(&'b mut self, types: &'a [ReleaseType]) -> &'b mut CountryQuery<'a> {
-        self.types = Some(types);
+    pub fn with_types<'b>(
+        &'b mut self,
+        types: impl IntoQueryList<'a, ReleaseType>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.types = Some(ReleaseType::known_only(types.into_query_list()));
         self
     }
 
-    ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
+    /// Filter materials by categories, expanded to the underlying release types. Replaces the types set by `with_types`
+    pub fn with_category<'b>(&'b mut self, categories: &[Category]) -> &'b mut CountryQuery<'a> {
+        self.types = Some(Cow::Owned(Category::release_types_of(categories)));
+        self
+    }
 
-    pub fn with_year<'b>(&'b mut self, year: &'a [u32]) -> &'b mut CountryQuery<'a> {
-        self.year = Some(year);
+    ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
+    ///
+    pub fn with_year<'b>(
+        &'b mut self,
+        year: impl IntoQueryList<'a, Year>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.year = Some(year.into_query_list());
         self
     }
 
     /// Filtering materials by translation ID
     pub fn with_translation_id<'b>(
         &'b mut self,
-        translation_id: &'a [u32],
+        translation_id: impl IntoQueryList<'a, u32>,
     ) -> &'b mut CountryQuery<'a> {
-        self.translation_id = Some(translation_id);
+        self.translation_id = Some(translation_id.into_query_list());
         self
     }
     /// Filter content by translation type. Allows you to output only voice translation or only subtitles
     pub fn with_translation_type<'b>(
         &'b mut self,
-        translation_type: &'a [TranslationType],
+        translation_type: impl IntoQueryList<'a, TranslationType>,
     ) -> &'b mut CountryQuery<'a> {
-        self.translation_type = Some(translation_type);
+        self.translation_type = Some(translation_type.into_query_list());
         self
     }
 
     /// Filtering materials based on the presence of a specific field. Materials that have at least one of the listed fields are shown. In order to show only materials that have all the listed fields
     pub fn with_has_field<'b>(
         &'b mut self,
-        has_field: &'a [MaterialDataField],
+        has_field: impl IntoQueryList<'a, MaterialDataField>,
     ) -> &'b mut CountryQuery<'a> {
-        self.has_field = Some(has_field);
+        self.has_field = Some(has_field.into_query_list());
         self
     }
     /// Filtering materials based on the presence of a specific field. Materials that have all the listed fields are shown
     pub fn with_has_field_and<'b>(
         &'b mut self,
-        has_field: &'a [MaterialDataField],
+        has_field: impl IntoQueryList<'a, MaterialDataField>,
     ) -> &'b mut CountryQuery<'a> {
-        self.has_field_and = Some(has_field);
+        self.has_field_and = Some(has_field.into_query_list());
         self
     }
 
     /// Filtering materials by country. You can specify a single value or multiple values, separated by commas (then materials with at least one of the listed countries will be displayed). The parameter is case sensitive
-    pub fn with_countries<'b>(&'b mut self, countries: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.countries = Some(countries);
+    pub fn with_countries<'b>(
+        &'b mut self,
+        countries: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.countries = Some(countries.into_query_list());
         self
     }
 
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
-    pub fn with_genres<'b>(&'b mut self, genres: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.genres = Some(genres);
+    pub fn with_genres<'b>(
+        &'b mut self,
+        genres: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.genres = Some(genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     pub fn with_anime_genres<'b>(
         &'b mut self,
-        anime_genres: &'a [&'a str],
+        anime_genres: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.anime_genres = Some(anime_genres);
+        self.anime_genres = Some(anime_genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     pub fn with_drama_genres<'b>(
         &'b mut self,
-        drama_genres: &'a [&'a str],
+        drama_genres: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.drama_genres = Some(drama_genres);
+        self.drama_genres = Some(drama_genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     pub fn with_all_genres<'b>(
         &'b mut self,
-        all_genres: &'a [&'a str],
+        all_genres: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.all_genres = Some(all_genres);
+        self.all_genres = Some(all_genres.into_query_list());
         self
     }
 
     /// Filtering by duration (in minutes). You can specify either a single value to search for the exact duration, or an interval.
-    pub fn with_duration<'b>(&'b mut self, duration: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.duration = Some(duration);
+    pub fn with_duration<'b>(
+        &'b mut self,
+        duration: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.duration = Some(duration.into_query_list());
         self
     }
 
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_kinopoisk_rating<'b>(
         &'b mut self,
-        kinopoisk_rating: &'a [&'a str],
+        kinopoisk_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.kinopoisk_rating = Some(kinopoisk_rating);
+        self.kinopoisk_rating = Some(kinopoisk_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_imdb_rating<'b>(
         &'b mut self,
-        imdb_rating: &'a [&'a str],
+        imdb_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.imdb_rating = Some(imdb_rating);
+        self.imdb_rating = Some(imdb_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_shikimori_rating<'b>(
         &'b mut self,
-        shikimori_rating: &'a [&'a str],
+        shikimori_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.shikimori_rating = Some(shikimori_rating);
+        self.shikimori_rating = Some(shikimori_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_mydramalist_rating<'b>(
         &'b mut self,
-        mydramalist_rating: &'a [&'a str],
+        mydramalist_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.mydramalist_rating = Some(mydramalist_rating);
+        self.mydramalist_rating = Some(mydramalist_rating.into_query_list());
         self
     }
 
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_actors<'b>(&'b mut self, actors: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.actors = Some(actors);
+    pub fn with_actors<'b>(
+        &'b mut self,
+        actors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.actors = Some(actors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_directors<'b>(&'b mut self, directors: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.directors = Some(directors);
+    pub fn with_directors<'b>(
+        &'b mut self,
+        directors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.directors = Some(directors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_producers<'b>(&'b mut self, producers: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.producers = Some(producers);
+    pub fn with_producers<'b>(
+        &'b mut self,
+        producers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.producers = Some(producers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_writers<'b>(&'b mut self, writers: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.writers = Some(writers);
+    pub fn with_writers<'b>(
+        &'b mut self,
+        writers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.writers = Some(writers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_composers<'b>(&'b mut self, composers: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.composers = Some(composers);
+    pub fn with_composers<'b>(
+        &'b mut self,
+        composers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.composers = Some(composers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_editors<'b>(&'b mut self, editors: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.editors = Some(editors);
+    pub fn with_editors<'b>(
+        &'b mut self,
+        editors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.editors = Some(editors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_designers<'b>(&'b mut self, designers: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.designers = Some(designers);
+    pub fn with_designers<'b>(
+        &'b mut self,
+        designers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.designers = Some(designers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_operators<'b>(&'b mut self, operators: &'a [&'a str]) -> &'b mut CountryQuery<'a> {
-        self.operators = Some(operators);
+    pub fn with_operators<'b>(
+        &'b mut self,
+        operators: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut CountryQuery<'a> {
+        self.operators = Some(operators.into_query_list());
         self
     }
 
     /// Filtering materials by age rating. You can specify a single value or multiple values, separated by commas. The parameter is case-insensitive
     pub fn with_rating_mpaa<'b>(
         &'b mut self,
-        rating_mpaa: &'a [MppaRating],
+        rating_mpaa: impl IntoQueryList<'a, MppaRating>,
     ) -> &'b mut CountryQuery<'a> {
-        self.rating_mpaa = Some(rating_mpaa);
+        self.rating_mpaa = Some(rating_mpaa.into_query_list());
         self
     }
 
     /// Filter content by the minimum age from which it can be viewed. You can specify either a single value or a range of values
     pub fn with_minimal_age<'b>(
         &'b mut self,
-        minimal_age: &'a [&'a str],
+        minimal_age: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.minimal_age = Some(minimal_age);
+        self.minimal_age = Some(minimal_age.into_query_list());
         self
     }
 
     /// Filtering materials by anime type. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     pub fn with_anime_kind<'b>(
         &'b mut self,
-        anime_kind: &'a [AnimeKind],
+        anime_kind: impl IntoQueryList<'a, AnimeKind>,
     ) -> &'b mut CountryQuery<'a> {
-        self.anime_kind = Some(anime_kind);
+        self.anime_kind = Some(anime_kind.into_query_list());
         self
     }
 
     /// Filters materials by MyDramaList tags. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     pub fn with_mydramalist_tags<'b>(
         &'b mut self,
-        mydramalist_tags: &'a [&'a str],
+        mydramalist_tags: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.mydramalist_tags = Some(mydramalist_tags);
+        self.mydramalist_tags = Some(mydramalist_tags.into_query_list());
         self
     }
 
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_anime_status<'b>(
         &'b mut self,
-        anime_status: &'a [AnimeStatus],
+        anime_status: impl IntoQueryList<'a, AnimeStatus>,
     ) -> &'b mut CountryQuery<'a> {
-        self.anime_status = Some(anime_status);
+        self.anime_status = Some(anime_status.into_query_list());
         self
     }
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_drama_status<'b>(
         &'b mut self,
-        drama_status: &'a [DramaStatus],
+        drama_status: impl IntoQueryList<'a, DramaStatus>,
     ) -> &'b mut CountryQuery<'a> {
-        self.drama_status = Some(drama_status);
+        self.drama_status = Some(drama_status.into_query_list());
         self
     }
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_all_status<'b>(
         &'b mut self,
-        all_status: &'a [AllStatus],
+        all_status: impl IntoQueryList<'a, AllStatus>,
     ) -> &'b mut CountryQuery<'a> {
-        self.all_status = Some(all_status);
+        self.all_status = Some(all_status.into_query_list());
         self
     }
 
     /// Filtering materials by anime studio. You can specify either one value or several values separated by commas (then materials with at least one of the listed studios will be displayed)
     pub fn with_anime_studios<'b>(
         &'b mut self,
-        anime_studios: &'a [&'a str],
+        anime_studios: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.anime_studios = Some(anime_studios);
+        self.anime_studios = Some(anime_studios.into_query_list());
         self
     }
     /// Filtering materials by license owner. You can specify a single value or several values separated by commas (then materials that have at least one of the listed owners will be displayed)
     pub fn with_anime_licensed_by<'b>(
         &'b mut self,
-        anime_licensed_by: &'a [&'a str],
+        anime_licensed_by: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut CountryQuery<'a> {
-        self.anime_licensed_by = Some(anime_licensed_by);
+        self.anime_licensed_by = Some(anime_licensed_by.into_query_list());
         self
     }
 
     /// Execute the query and fetch the results.
-    pub async fn execute<'b>(&'a self, client: &'b Client) -> Result<CountryResponse, Error> {
+    pub async fn execute(&self, client: &Client) -> Result<CountryResponse, Error> {
+        ReleaseType::validate_query_types(self.types.as_deref())?;
+        reject_commas_in_lists(self)?;
+
         let payload = serialize_into_query_parts(self)?;
 
-        let response = client
-            .init_post_request("/countries")
-            .query(&payload)
-            .send()
-            .await
-            .map_err(Error::HttpError)?;
+        let request_builder = client.init_post_request("/countries").query(&payload);
 
-        let result = response
-            .json::<CountryResponseUnion>()
-            .await
-            .map_err(Error::HttpError)?;
+        let result = client
+            .execute_request::<CountryResponseUnion>(request_builder)
+            .await?;
 
         match result {
             CountryResponseUnion::Result(result) => Ok(result),
             CountryResponseUnion::Error { error } => Err(Error::KodikError(error)),
         }
     }
+
+    /// Execute the query with the client it was created by, see [`Client::countries`]
+    ///
+    /// Fails with [`Error::InvalidQuery`] if the query was created with [`CountryQuery::new`], use [`CountryQuery::execute`] for it.
+    pub async fn send(&self) -> Result<CountryResponse, Error> {
+        let client = self.client.as_ref().ok_or_else(|| {
+            Error::InvalidQuery("the query is not bound to a client, use `execute`".to_owned())
+        })?;
+
+        self.execute(client).await
+    }
 }
 
 impl<'a> Default for CountryQuery<'a> {
@@ -474,3 +581,80 @@ impl<'a> Default for CountryQuery<'a> {
         Self::new()
     }
 }
+
+impl Client {
+    /// Create a [`CountryQuery`] bound to this client, so it can be executed with [`CountryQuery::send`]
+    pub fn countries<'a>(&self) -> CountryQuery<'a> {
+        CountryQuery {
+            client: Some(self.clone()),
+            ..CountryQuery::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transport::MockTransport, ClientBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_sort_from_str() {
+        for sort in [CountrySort::Title, CountrySort::Count] {
+            assert_eq!(sort.to_string().parse::<CountrySort>().unwrap(), sort);
+            assert_eq!(serde_json::to_value(sort).unwrap(), sort.as_str());
+        }
+
+        assert_eq!(CountrySort::default(), CountrySort::Title);
+
+        assert!(matches!(
+            "name".parse::<CountrySort>(),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_empty_response() {
+        let transport = MockTransport::new();
+
+        transport.respond(
+            "/countries",
+            200,
+            r#"{"time":"3ms","total":0,"prev_page":null,"next_page":null,"results":[]}"#,
+        );
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport.clone())
+            .build();
+
+        let response = CountryQuery::new().execute(&client).await.unwrap();
+
+        assert_eq!(response.total, 0);
+        assert!(response.results.is_empty());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_numbers_as_strings() {
+        for body in [
+            r#"{"time":"3ms","total":1,"results":[{"title":"Япония","count":5678}]}"#,
+            r#"{"time":"3ms","total":"1","results":[{"title":"Япония","count":"5678"}]}"#,
+        ] {
+            let result: CountryResponseUnion = serde_json::from_str(body).unwrap();
+
+            match result {
+                CountryResponseUnion::Result(result) => {
+                    assert_eq!(result.total, 1);
+                    assert_eq!(result.results[0].count, 5678);
+                }
+                CountryResponseUnion::Error { error } => panic!("unexpected error {error}"),
+            }
+        }
+
+        assert!(serde_json::from_str::<CountryResponseUnion>(
+            r#"{"time":"3ms","total":"many","results":[]}"#
+        )
+        .is_err());
+    }
+}
@@ -1,10 +1,36 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
 use thiserror::Error;
 
+use crate::RetryPolicy;
+
 #[derive(Error, Debug)]
 #[non_exhaustive]
 pub enum Error {
     #[error("HTTP request failed: {}", .0)]
     HttpError(reqwest::Error),
+    /// The API responded with a non-success status, the body is truncated if it is too long
+    #[error("HTTP status {status}: {body}")]
+    HttpStatus {
+        status: u16,
+        body: String,
+        headers: HeaderMap,
+    },
+
+    /// The response body is not the expected JSON, the body is truncated if it is too long
+    #[error("Error decode response: {source}")]
+    DecodeError {
+        source: serde_json::Error,
+        body: String,
+    },
+
     #[error("Error urlencoded serialize: {}", .0)]
     UrlencodedSerializeError(comma_serde_urlencoded::ser::Error),
     #[error("Error urlencoded deserialize: {}", .0)]
@@ -12,4 +38,314 @@ pub enum Error {
 
     #[error("Kodik error: {}", .0)]
     KodikError(String),
+
+    #[error("Invalid parameter: {}", .0)]
+    InvalidParameter(String),
+
+    /// The query combines parameters in a way the API doesn't accept, detected before sending the request
+    #[error("Invalid query: {}", .0)]
+    InvalidQuery(String),
+
+    /// Error of parsing a date from a release, see the `chrono` feature
+    #[cfg(feature = "chrono")]
+    #[error("Error date parse: {}", .0)]
+    DateParseError(chrono::ParseError),
+
+    /// Error of a request that was shared between several identical concurrent calls, see [`crate::ClientBuilder::single_flight`]
+    #[error("Shared request failed: {}", .0)]
+    SharedRequestError(Arc<Error>),
+
+    #[error("API key is missing, set the KODIK_API_KEY environment variable")]
+    MissingApiKey,
+}
+
+impl Error {
+    /// Returns the kind of the Kodik error, or `None` if the error didn't come from the API
+    ///
+    /// Looks through [`Error::SharedRequestError`] as well.
+    pub fn kind(&self) -> Option<KodikErrorKind> {
+        match self {
+            Error::KodikError(message) => Some(KodikErrorKind::from_message(message)),
+            Error::SharedRequestError(err) => err.kind(),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the request may succeed if it is sent again later
+    ///
+    /// True for transient network errors, server errors (5xx) and rate limits, see [`crate::RetryPolicy::is_transient`] and [`Error::is_rate_limited`]. Looks through [`Error::SharedRequestError`] as well.
+    ///
+    /// ```
+    /// use kodik_api::error::Error;
+    ///
+    /// let err = Error::HttpStatus {
+    ///     status: 503,
+    ///     body: "Service Unavailable".to_owned(),
+    ///     headers: Default::default(),
+    /// };
+    ///
+    /// assert!(err.is_retryable());
+    /// assert!(!Error::KodikError("Неправильный тип".to_owned()).is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::SharedRequestError(err) => err.is_retryable(),
+            err => RetryPolicy::is_transient(err) || err.is_rate_limited(),
+        }
+    }
+
+    /// Checks whether too many requests were sent, either by the `429` status or by the message of a Kodik error
+    ///
+    /// Looks through [`Error::SharedRequestError`] as well.
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            Error::HttpError(err) => err.status() == Some(StatusCode::TOO_MANY_REQUESTS),
+            Error::HttpStatus { status, .. } => *status == StatusCode::TOO_MANY_REQUESTS.as_u16(),
+            Error::KodikError(_) => self.kind() == Some(KodikErrorKind::RateLimited),
+            Error::SharedRequestError(err) => err.is_rate_limited(),
+            _ => false,
+        }
+    }
+
+    /// Returns the delay requested by the `Retry-After` header of an [`Error::HttpStatus`], usually sent with the `429` and `503` statuses
+    ///
+    /// Both the number of seconds and the HTTP date forms are supported, a date in the past is a zero delay. Looks through [`Error::SharedRequestError`] as well.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::HttpStatus { headers, .. } => {
+                parse_retry_after(headers.get(RETRY_AFTER)?.to_str().ok()?, SystemTime::now())
+            }
+            Error::SharedRequestError(err) => err.retry_after(),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the value of the `Retry-After` header, an HTTP date is counted from `now`
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+/// Kind of an error returned by the Kodik API, parsed from its message
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KodikErrorKind {
+    /// The API key is missing or wrong, for example `Отсутствует или неверный токен`
+    InvalidToken,
+
+    /// Too many requests were sent, worth retrying later
+    RateLimited,
+
+    /// The requested material doesn't exist
+    NotFound,
+
+    /// Any other message
+    Other(String),
+}
+
+impl KodikErrorKind {
+    /// Parses the kind from an error message of the API
+    ///
+    /// ```
+    /// use kodik_api::error::KodikErrorKind;
+    ///
+    /// assert_eq!(
+    ///     KodikErrorKind::from_message("Отсутствует или неверный токен"),
+    ///     KodikErrorKind::InvalidToken
+    /// );
+    /// ```
+    pub fn from_message(message: &str) -> KodikErrorKind {
+        let lowercase_message = message.to_lowercase();
+
+        if lowercase_message.contains("токен") || lowercase_message.contains("token") {
+            KodikErrorKind::InvalidToken
+        } else if lowercase_message.contains("лимит")
+            || lowercase_message.contains("слишком много запросов")
+            || lowercase_message.contains("too many requests")
+        {
+            KodikErrorKind::RateLimited
+        } else if lowercase_message.contains("не найден") || lowercase_message.contains("not found")
+        {
+            KodikErrorKind::NotFound
+        } else {
+            KodikErrorKind::Other(message.to_owned())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kodik_error_kind() {
+        for (message, kind) in [
+            (
+                "Отсутствует или неверный токен",
+                KodikErrorKind::InvalidToken,
+            ),
+            ("Превышен лимит запросов", KodikErrorKind::RateLimited),
+            ("Слишком много запросов", KodikErrorKind::RateLimited),
+            ("Материал не найден", KodikErrorKind::NotFound),
+            (
+                "Неправильный тип",
+                KodikErrorKind::Other("Неправильный тип".to_owned()),
+            ),
+        ] {
+            assert_eq!(KodikErrorKind::from_message(message), kind, "{message}");
+        }
+    }
+
+    #[test]
+    fn test_kind() {
+        let err = Error::KodikError("Отсутствует или неверный токен".to_owned());
+
+        assert_eq!(err.kind(), Some(KodikErrorKind::InvalidToken));
+        assert_eq!(
+            err.to_string(),
+            "Kodik error: Отсутствует или неверный токен"
+        );
+
+        let err = Error::SharedRequestError(Arc::new(Error::KodikError(
+            "Превышен лимит запросов".to_owned(),
+        )));
+
+        assert_eq!(err.kind(), Some(KodikErrorKind::RateLimited));
+        assert_eq!(Error::MissingApiKey.kind(), None);
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        for (err, is_retryable, is_rate_limited) in [
+            (
+                Error::HttpStatus {
+                    status: 503,
+                    body: String::new(),
+                    headers: HeaderMap::new(),
+                },
+                true,
+                false,
+            ),
+            (
+                Error::HttpStatus {
+                    status: 429,
+                    body: String::new(),
+                    headers: HeaderMap::new(),
+                },
+                true,
+                true,
+            ),
+            (
+                Error::HttpStatus {
+                    status: 400,
+                    body: String::new(),
+                    headers: HeaderMap::new(),
+                },
+                false,
+                false,
+            ),
+            (
+                Error::KodikError("Превышен лимит запросов".to_owned()),
+                true,
+                true,
+            ),
+            (
+                Error::KodikError("Отсутствует или неверный токен".to_owned()),
+                false,
+                false,
+            ),
+            (
+                Error::SharedRequestError(Arc::new(Error::HttpStatus {
+                    status: 502,
+                    body: String::new(),
+                    headers: HeaderMap::new(),
+                })),
+                true,
+                false,
+            ),
+            (
+                Error::SharedRequestError(Arc::new(Error::KodikError(
+                    "Слишком много запросов".to_owned(),
+                ))),
+                true,
+                true,
+            ),
+            (
+                Error::DecodeError {
+                    source: serde_json::from_str::<()>("{").unwrap_err(),
+                    body: "{".to_owned(),
+                },
+                false,
+                false,
+            ),
+            (Error::InvalidParameter("sort".to_owned()), false, false),
+            (Error::InvalidQuery("limit".to_owned()), false, false),
+            (Error::MissingApiKey, false, false),
+        ] {
+            assert_eq!(err.is_retryable(), is_retryable, "{err}");
+            assert_eq!(err.is_rate_limited(), is_rate_limited, "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_is_retryable_http_error() {
+        // Nothing listens on the port, the connection is refused
+        let err = Error::HttpError(
+            reqwest::Client::new()
+                .get("http://127.0.0.1:1")
+                .send()
+                .await
+                .unwrap_err(),
+        );
+
+        assert!(err.is_retryable());
+        assert!(!err.is_rate_limited());
+
+        let err = Error::HttpError(reqwest::Client::new().get("not a url").build().unwrap_err());
+
+        assert!(!err.is_retryable());
+        assert!(!err.is_rate_limited());
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let now = httpdate::parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+
+        assert_eq!(parse_retry_after("2", now), Some(Duration::from_secs(2)));
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:49:47 GMT", now),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 06 Nov 1994 08:49:27 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("-2", now), None);
+        assert_eq!(parse_retry_after("soon", now), None);
+
+        let mut headers = HeaderMap::new();
+
+        headers.insert(RETRY_AFTER, "2".parse().unwrap());
+
+        let err = Error::HttpStatus {
+            status: 429,
+            body: String::new(),
+            headers,
+        };
+
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(2)));
+        assert_eq!(
+            Error::SharedRequestError(Arc::new(err)).retry_after(),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(Error::MissingApiKey.retry_after(), None);
+    }
 }
@@ -1,12 +1,17 @@
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
     error::Error,
     types::{
-        AllStatus, AnimeKind, AnimeStatus, DramaStatus, MaterialDataField, MppaRating, ReleaseType,
-        TranslationType,
+        AllStatus, AnimeKind, AnimeStatus, Category, DramaStatus, IntoQueryList, MaterialDataField,
+        MppaRating, ProcessingTime, ReleaseType, TranslationType, Year,
+    },
+    util::{
+        deserialize_number_or_string, reject_commas_in_lists, serialize_into_query_parts,
+        ResponseUnion,
     },
-    util::serialize_into_query_parts,
     Client,
 };
 
@@ -16,19 +21,27 @@ pub struct GenreResult {
     pub title: String,
 
     /// The number of materials with this voice acting
+    #[serde(deserialize_with = "deserialize_number_or_string")]
     pub count: i32,
 }
 
 /// A struct containing genres results and other information about the genres
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GenreResponse {
     pub time: String,
+    #[serde(deserialize_with = "deserialize_number_or_string")]
     pub total: i32,
     pub prev_page: Option<String>,
     pub next_page: Option<String>,
     pub results: Vec<GenreResult>,
 }
 
+impl ProcessingTime for GenreResponse {
+    fn time(&self) -> &str {
+        &self.time
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(untagged)]
 enum GenreResponseUnion {
@@ -36,6 +49,12 @@ enum GenreResponseUnion {
     Error { error: String },
 }
 
+impl ResponseUnion for GenreResponseUnion {
+    fn is_error(&self) -> bool {
+        matches!(self, GenreResponseUnion::Error { .. })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum GenreSort {
     #[serde(rename = "title")]
@@ -68,117 +87,121 @@ pub struct GenreQuery<'a> {
 
     /// Maximum number of outputs
     #[serde(skip_serializing_if = "Option::is_none")]
-    types: Option<&'a [ReleaseType]>,
+    types: Option<Cow<'a, [ReleaseType]>>,
 
     ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
     #[serde(skip_serializing_if = "Option::is_none")]
-    year: Option<&'a [u32]>,
+    year: Option<Cow<'a, [Year]>>,
 
     /// Filtering materials by translation ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    translation_id: Option<&'a [u32]>,
+    translation_id: Option<Cow<'a, [u32]>>,
     /// Filter content by translation type. Allows you to output only voice translation or only subtitles
     #[serde(skip_serializing_if = "Option::is_none")]
-    translation_type: Option<&'a [TranslationType]>,
+    translation_type: Option<Cow<'a, [TranslationType]>>,
 
     /// Filtering materials based on the presence of a specific field. Materials that have at least one of the listed fields are shown. In order to show only materials that have all the listed fields
     #[serde(skip_serializing_if = "Option::is_none")]
-    has_field: Option<&'a [MaterialDataField]>,
+    has_field: Option<Cow<'a, [MaterialDataField]>>,
     /// Filtering materials based on the presence of a specific field. Materials that have all the listed fields are shown
     #[serde(skip_serializing_if = "Option::is_none")]
-    has_field_and: Option<&'a [MaterialDataField]>,
+    has_field_and: Option<Cow<'a, [MaterialDataField]>>,
 
     /// Filtering materials by country. You can specify a single value or multiple values, separated by commas (then materials with at least one of the listed countries will be displayed). The parameter is case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    countries: Option<&'a [&'a str]>,
+    countries: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    genres: Option<&'a [&'a str]>,
+    genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_genres: Option<&'a [&'a str]>,
+    anime_genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    drama_genres: Option<&'a [&'a str]>,
+    drama_genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    all_genres: Option<&'a [&'a str]>,
+    all_genres: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by duration (in minutes). You can specify either a single value to search for the exact duration, or an interval.
     #[serde(skip_serializing_if = "Option::is_none")]
-    duration: Option<&'a [&'a str]>,
+    duration: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    kinopoisk_rating: Option<&'a [&'a str]>,
+    kinopoisk_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    imdb_rating: Option<&'a [&'a str]>,
+    imdb_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    shikimori_rating: Option<&'a [&'a str]>,
+    shikimori_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    mydramalist_rating: Option<&'a [&'a str]>,
+    mydramalist_rating: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    actors: Option<&'a [&'a str]>,
+    actors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    directors: Option<&'a [&'a str]>,
+    directors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    producers: Option<&'a [&'a str]>,
+    producers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    writers: Option<&'a [&'a str]>,
+    writers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    composers: Option<&'a [&'a str]>,
+    composers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    editors: Option<&'a [&'a str]>,
+    editors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    designers: Option<&'a [&'a str]>,
+    designers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    operators: Option<&'a [&'a str]>,
+    operators: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by age rating. You can specify a single value or multiple values, separated by commas. The parameter is case-insensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    rating_mpaa: Option<&'a [MppaRating]>,
+    rating_mpaa: Option<Cow<'a, [MppaRating]>>,
 
     /// Filter content by the minimum age from which it can be viewed. You can specify either a single value or a range of values
     #[serde(skip_serializing_if = "Option::is_none")]
-    minimal_age: Option<&'a [&'a str]>,
+    minimal_age: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by anime type. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_kind: Option<&'a [AnimeKind]>,
+    anime_kind: Option<Cow<'a, [AnimeKind]>>,
 
     /// Filters materials by MyDramaList tags. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    mydramalist_tags: Option<&'a [&'a str]>,
+    mydramalist_tags: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_status: Option<&'a [AnimeStatus]>,
+    anime_status: Option<Cow<'a, [AnimeStatus]>>,
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    drama_status: Option<&'a [DramaStatus]>,
+    drama_status: Option<Cow<'a, [DramaStatus]>>,
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    all_status: Option<&'a [AllStatus]>,
+    all_status: Option<Cow<'a, [AllStatus]>>,
 
     /// Filtering materials by anime studio. You can specify either one value or several values separated by commas (then materials with at least one of the listed studios will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_studios: Option<&'a [&'a str]>,
+    anime_studios: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by license owner. You can specify a single value or several values separated by commas (then materials that have at least one of the listed owners will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_licensed_by: Option<&'a [&'a str]>,
+    anime_licensed_by: Option<Cow<'a, [Cow<'a, str>]>>,
+
+    /// Client bound by [`Client::genres`], used by [`GenreQuery::send`]
+    #[serde(skip)]
+    client: Option<Client>,
 }
 
 impl<'a> GenreQuery<'a> {
@@ -219,6 +242,7 @@ impl<'a> GenreQuery<'a> {
             all_status: None,
             anime_studios: None,
             anime_licensed_by: None,
+            client: None,
         }
     }
 
@@ -229,263 +253,319 @@ impl<'a> GenreQuery<'a> {
     }
 
     /// Maximum number of outputs
-    pub fn with_types<'b>(&'b mut self, types: &'a [ReleaseType]) -> &'b mut GenreQuery<'a> {
-        self.types = Some(types);
+    pub fn with_types<'b>(
+        &'b mut self,
+        types: impl IntoQueryList<'a, ReleaseType>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.types = Some(ReleaseType::known_only(types.into_query_list()));
+        self
+    }
+
+    /// Filter materials by categories, expanded to the underlying release types. Replaces the types set by `with_types`
+    pub fn with_category<'b>(&'b mut self, categories: &[Category]) -> &'b mut GenreQuery<'a> {
+        self.types = Some(Cow::Owned(Category::release_types_of(categories)));
         self
     }
 
     ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
-    pub fn with_year<'b>(&'b mut self, year: &'a [u32]) -> &'b mut GenreQuery<'a> {
-        self.year = Some(year);
+    pub fn with_year<'b>(
+        &'b mut self,
+        year: impl IntoQueryList<'a, Year>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.year = Some(year.into_query_list());
         self
     }
 
     /// Filtering materials by translation ID
     pub fn with_translation_id<'b>(
         &'b mut self,
-        translation_id: &'a [u32],
+        translation_id: impl IntoQueryList<'a, u32>,
     ) -> &'b mut GenreQuery<'a> {
-        self.translation_id = Some(translation_id);
+        self.translation_id = Some(translation_id.into_query_list());
         self
     }
     /// Filter content by translation type. Allows you to output only voice translation or only subtitles
     pub fn with_translation_type<'b>(
         &'b mut self,
-        translation_type: &'a [TranslationType],
+        translation_type: impl IntoQueryList<'a, TranslationType>,
     ) -> &'b mut GenreQuery<'a> {
-        self.translation_type = Some(translation_type);
+        self.translation_type = Some(translation_type.into_query_list());
         self
     }
 
     /// Filtering materials based on the presence of a specific field. Materials that have at least one of the listed fields are shown. In order to show only materials that have all the listed fields
     pub fn with_has_field<'b>(
         &'b mut self,
-        has_field: &'a [MaterialDataField],
+        has_field: impl IntoQueryList<'a, MaterialDataField>,
     ) -> &'b mut GenreQuery<'a> {
-        self.has_field = Some(has_field);
+        self.has_field = Some(has_field.into_query_list());
         self
     }
     /// Filtering materials based on the presence of a specific field. Materials that have all the listed fields are shown
     pub fn with_has_field_and<'b>(
         &'b mut self,
-        has_field: &'a [MaterialDataField],
+        has_field: impl IntoQueryList<'a, MaterialDataField>,
     ) -> &'b mut GenreQuery<'a> {
-        self.has_field_and = Some(has_field);
+        self.has_field_and = Some(has_field.into_query_list());
         self
     }
 
     /// Filtering materials by country. You can specify a single value or multiple values, separated by commas (then materials with at least one of the listed countries will be displayed). The parameter is case sensitive
-    pub fn with_countries<'b>(&'b mut self, countries: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.countries = Some(countries);
+    pub fn with_countries<'b>(
+        &'b mut self,
+        countries: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.countries = Some(countries.into_query_list());
         self
     }
 
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
-    pub fn with_genres<'b>(&'b mut self, genres: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.genres = Some(genres);
+    pub fn with_genres<'b>(
+        &'b mut self,
+        genres: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.genres = Some(genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     pub fn with_anime_genres<'b>(
         &'b mut self,
-        anime_genres: &'a [&'a str],
+        anime_genres: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.anime_genres = Some(anime_genres);
+        self.anime_genres = Some(anime_genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     pub fn with_drama_genres<'b>(
         &'b mut self,
-        drama_genres: &'a [&'a str],
+        drama_genres: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.drama_genres = Some(drama_genres);
+        self.drama_genres = Some(drama_genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
-    pub fn with_all_genres<'b>(&'b mut self, all_genres: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.all_genres = Some(all_genres);
+    pub fn with_all_genres<'b>(
+        &'b mut self,
+        all_genres: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.all_genres = Some(all_genres.into_query_list());
         self
     }
 
     /// Filtering by duration (in minutes). You can specify either a single value to search for the exact duration, or an interval.
-    pub fn with_duration<'b>(&'b mut self, duration: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.duration = Some(duration);
+    pub fn with_duration<'b>(
+        &'b mut self,
+        duration: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.duration = Some(duration.into_query_list());
         self
     }
 
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_kinopoisk_rating<'b>(
         &'b mut self,
-        kinopoisk_rating: &'a [&'a str],
+        kinopoisk_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.kinopoisk_rating = Some(kinopoisk_rating);
+        self.kinopoisk_rating = Some(kinopoisk_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_imdb_rating<'b>(
         &'b mut self,
-        imdb_rating: &'a [&'a str],
+        imdb_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.imdb_rating = Some(imdb_rating);
+        self.imdb_rating = Some(imdb_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_shikimori_rating<'b>(
         &'b mut self,
-        shikimori_rating: &'a [&'a str],
+        shikimori_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.shikimori_rating = Some(shikimori_rating);
+        self.shikimori_rating = Some(shikimori_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_mydramalist_rating<'b>(
         &'b mut self,
-        mydramalist_rating: &'a [&'a str],
+        mydramalist_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.mydramalist_rating = Some(mydramalist_rating);
+        self.mydramalist_rating = Some(mydramalist_rating.into_query_list());
         self
     }
 
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_actors<'b>(&'b mut self, actors: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.actors = Some(actors);
+    pub fn with_actors<'b>(
+        &'b mut self,
+        actors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.actors = Some(actors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_directors<'b>(&'b mut self, directors: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.directors = Some(directors);
+    pub fn with_directors<'b>(
+        &'b mut self,
+        directors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.directors = Some(directors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_producers<'b>(&'b mut self, producers: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.producers = Some(producers);
+    pub fn with_producers<'b>(
+        &'b mut self,
+        producers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.producers = Some(producers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_writers<'b>(&'b mut self, writers: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.writers = Some(writers);
+    pub fn with_writers<'b>(
+        &'b mut self,
+        writers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.writers = Some(writers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_composers<'b>(&'b mut self, composers: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.composers = Some(composers);
+    pub fn with_composers<'b>(
+        &'b mut self,
+        composers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.composers = Some(composers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_editors<'b>(&'b mut self, editors: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.editors = Some(editors);
+    pub fn with_editors<'b>(
+        &'b mut self,
+        editors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.editors = Some(editors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_designers<'b>(&'b mut self, designers: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.designers = Some(designers);
+    pub fn with_designers<'b>(
+        &'b mut self,
+        designers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.designers = Some(designers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_operators<'b>(&'b mut self, operators: &'a [&'a str]) -> &'b mut GenreQuery<'a> {
-        self.operators = Some(operators);
+    pub fn with_operators<'b>(
+        &'b mut self,
+        operators: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut GenreQuery<'a> {
+        self.operators = Some(operators.into_query_list());
         self
     }
 
     /// Filtering materials by age rating. You can specify a single value or multiple values, separated by commas. The parameter is case-insensitive
     pub fn with_rating_mpaa<'b>(
         &'b mut self,
-        rating_mpaa: &'a [MppaRating],
+        rating_mpaa: impl IntoQueryList<'a, MppaRating>,
     ) -> &'b mut GenreQuery<'a> {
-        self.rating_mpaa = Some(rating_mpaa);
+        self.rating_mpaa = Some(rating_mpaa.into_query_list());
         self
     }
 
     /// Filter content by the minimum age from which it can be viewed. You can specify either a single value or a range of values
     pub fn with_minimal_age<'b>(
         &'b mut self,
-        minimal_age: &'a [&'a str],
+        minimal_age: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.minimal_age = Some(minimal_age);
+        self.minimal_age = Some(minimal_age.into_query_list());
         self
     }
 
     /// Filtering materials by anime type. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     pub fn with_anime_kind<'b>(
         &'b mut self,
-        anime_kind: &'a [AnimeKind],
+        anime_kind: impl IntoQueryList<'a, AnimeKind>,
     ) -> &'b mut GenreQuery<'a> {
-        self.anime_kind = Some(anime_kind);
+        self.anime_kind = Some(anime_kind.into_query_list());
         self
     }
 
     /// Filters materials by MyDramaList tags. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     pub fn with_mydramalist_tags<'b>(
         &'b mut self,
-        mydramalist_tags: &'a [&'a str],
+        mydramalist_tags: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.mydramalist_tags = Some(mydramalist_tags);
+        self.mydramalist_tags = Some(mydramalist_tags.into_query_list());
         self
     }
 
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_anime_status<'b>(
         &'b mut self,
-        anime_status: &'a [AnimeStatus],
+        anime_status: impl IntoQueryList<'a, AnimeStatus>,
     ) -> &'b mut GenreQuery<'a> {
-        self.anime_status = Some(anime_status);
+        self.anime_status = Some(anime_status.into_query_list());
         self
     }
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_drama_status<'b>(
         &'b mut self,
-        drama_status: &'a [DramaStatus],
+        drama_status: impl IntoQueryList<'a, DramaStatus>,
     ) -> &'b mut GenreQuery<'a> {
-        self.drama_status = Some(drama_status);
+        self.drama_status = Some(drama_status.into_query_list());
         self
     }
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_all_status<'b>(
         &'b mut self,
-        all_status: &'a [AllStatus],
+        all_status: impl IntoQueryList<'a, AllStatus>,
     ) -> &'b mut GenreQuery<'a> {
-        self.all_status = Some(all_status);
+        self.all_status = Some(all_status.into_query_list());
         self
     }
 
     /// Filtering materials by anime studio. You can specify either one value or several values separated by commas (then materials with at least one of the listed studios will be displayed)
     pub fn with_anime_studios<'b>(
         &'b mut self,
-        anime_studios: &'a [&'a str],
+        anime_studios: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.anime_studios = Some(anime_studios);
+        self.anime_studios = Some(anime_studios.into_query_list());
         self
     }
     /// Filtering materials by license owner. You can specify a single value or several values separated by commas (then materials that have at least one of the listed owners will be displayed)
     pub fn with_anime_licensed_by<'b>(
         &'b mut self,
-        anime_licensed_by: &'a [&'a str],
+        anime_licensed_by: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut GenreQuery<'a> {
-        self.anime_licensed_by = Some(anime_licensed_by);
+        self.anime_licensed_by = Some(anime_licensed_by.into_query_list());
         self
     }
 
     /// Execute the query and fetch the results.
-    pub async fn execute<'b>(&'a self, client: &'b Client) -> Result<GenreResponse, Error> {
+    pub async fn execute(&self, client: &Client) -> Result<GenreResponse, Error> {
+        ReleaseType::validate_query_types(self.types.as_deref())?;
+        reject_commas_in_lists(self)?;
+
         let payload = serialize_into_query_parts(self)?;
 
-        let response = client
-            .init_post_request("/genres")
-            .query(&payload)
-            .send()
-            .await
-            .map_err(Error::HttpError)?;
+        let request_builder = client.init_post_request("/genres").query(&payload);
 
-        let result = response
-            .json::<GenreResponseUnion>()
-            .await
-            .map_err(Error::HttpError)?;
+        let result = client
+            .execute_request::<GenreResponseUnion>(request_builder)
+            .await?;
 
         match result {
             GenreResponseUnion::Result(result) => Ok(result),
             GenreResponseUnion::Error { error } => Err(Error::KodikError(error)),
         }
     }
+
+    /// Execute the query with the client it was created by, see [`Client::genres`]
+    ///
+    /// Fails with [`Error::InvalidQuery`] if the query was created with [`GenreQuery::new`], use [`GenreQuery::execute`] for it.
+    pub async fn send(&self) -> Result<GenreResponse, Error> {
+        let client = self.client.as_ref().ok_or_else(|| {
+            Error::InvalidQuery("the query is not bound to a client, use `execute`".to_owned())
+        })?;
+
+        self.execute(client).await
+    }
 }
 
 impl<'a> Default for GenreQuery<'a> {
@@ -493,3 +573,101 @@ impl<'a> Default for GenreQuery<'a> {
         Self::new()
     }
 }
+
+impl Client {
+    /// Create a [`GenreQuery`] bound to this client, so it can be executed with [`GenreQuery::send`]
+    pub fn genres<'a>(&self) -> GenreQuery<'a> {
+        GenreQuery {
+            client: Some(self.clone()),
+            ..GenreQuery::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{transport::MockTransport, ClientBuilder};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_empty_response() {
+        let transport = MockTransport::new();
+
+        transport.respond(
+            "/genres",
+            200,
+            r#"{"time":"3ms","total":0,"prev_page":null,"next_page":null,"results":[]}"#,
+        );
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport.clone())
+            .build();
+
+        let response = GenreQuery::new().execute(&client).await.unwrap();
+
+        assert_eq!(response.total, 0);
+        assert!(response.results.is_empty());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_response() {
+        let result: GenreResponseUnion = serde_json::from_str(
+            r#"{"time":"2ms","total":2,"prev_page":null,"next_page":null,"results":[{"title":"аниме","count":4321},{"title":"комедия","count":1234}]}"#,
+        )
+        .unwrap();
+
+        match result {
+            GenreResponseUnion::Result(result) => {
+                assert_eq!(result.total, 2);
+                assert_eq!(
+                    result
+                        .results
+                        .iter()
+                        .map(|genre| (genre.title.as_str(), genre.count))
+                        .collect::<Vec<_>>(),
+                    [("аниме", 4321), ("комедия", 1234)]
+                );
+            }
+            GenreResponseUnion::Error { error } => panic!("unexpected error {error}"),
+        }
+
+        let result: GenreResponseUnion =
+            serde_json::from_str(r#"{"error":"Отсутствует или неверный токен"}"#).unwrap();
+
+        assert!(result.is_error());
+    }
+
+    #[tokio::test]
+    async fn test_execute_unknown_types_only() {
+        let client = Client::new("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7");
+
+        let result = GenreQuery::new()
+            .with_types(&[ReleaseType::Unknown])
+            .execute(&client)
+            .await;
+
+        assert!(matches!(result, Err(Error::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_query() {
+        let mut query = GenreQuery::new();
+
+        query
+            .with_genres_type(GenreType::Shikimori)
+            .with_types(&[ReleaseType::Anime, ReleaseType::AnimeSerial])
+            .with_translation_type(&[TranslationType::Voice]);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [
+                ("genres_type".to_owned(), "shikimori".to_owned()),
+                ("types".to_owned(), "anime,anime-serial".to_owned()),
+                ("translation_type".to_owned(), "voice".to_owned()),
+            ]
+        );
+    }
+}
@@ -8,9 +8,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let api_key = std::env::var("KODIK_API_KEY").expect("KODIK_API_KEY is not set");
-//!
-//!     let client = Client::new(api_key);
+//!     let client = Client::from_env().expect("KODIK_API_KEY is not set");
 //!
 //!     let search_response = SearchQuery::new()
 //!         .with_title("Cyberpunk: Edgerunners")
@@ -34,9 +32,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let api_key = std::env::var("KODIK_API_KEY").expect("KODIK_API_KEY is not set");
-//!
-//!     let client = Client::new(api_key);
+//!     let client = Client::from_env().expect("KODIK_API_KEY is not set");
 //!
 //!     let stream = ListQuery::new()
 //!         .with_limit(100)
@@ -52,20 +48,14 @@
 //!                 dbg!(response.results);
 //!             }
 //!             Err(err) => {
-//!                 match err {
-//!                     // Kodik error
-//!                     kodik_api::error::Error::KodikError(message) => {
-//!                         panic!("kodik error = {}", message);
-//!                     }
-//!                     // Reqwest error
-//!                     kodik_api::error::Error::HttpError(_err) => {
-//!                         // Another try
-//!                         continue;
-//!                     }
-//!                     _ => {
-//!                         panic!("Unknown error")
-//!                     }
+//!                 // Network errors, server errors and rate limits
+//!                 if err.is_retryable() {
+//!                     tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+//!
+//!                     continue;
 //!                 }
+//!
+//!                 panic!("error = {err}");
 //!             }
 //!         }
 //!     }
@@ -80,9 +70,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let api_key = std::env::var("KODIK_API_KEY").expect("KODIK_API_KEY is not set");
-//!
-//!     let client = Client::new(api_key);
+//!     let client = Client::from_env().expect("KODIK_API_KEY is not set");
 //!
 //!     let translations_response = TranslationQuery::new()
 //!         .execute(&client)
@@ -101,9 +89,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let api_key = std::env::var("KODIK_API_KEY").expect("KODIK_API_KEY is not set");
-//!
-//!     let client = Client::new(api_key);
+//!     let client = Client::from_env().expect("KODIK_API_KEY is not set");
 //!
 //!     let genres_response = GenreQuery::new()
 //!         .execute(&client)
@@ -122,9 +108,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let api_key = std::env::var("KODIK_API_KEY").expect("KODIK_API_KEY is not set");
-//!
-//!     let client = Client::new(api_key);
+//!     let client = Client::from_env().expect("KODIK_API_KEY is not set");
 //!
 //!     let countries_response = CountryQuery::new()
 //!         .execute(&client)
@@ -143,9 +127,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let api_key = std::env::var("KODIK_API_KEY").expect("KODIK_API_KEY is not set");
-//!
-//!     let client = Client::new(api_key);
+//!     let client = Client::from_env().expect("KODIK_API_KEY is not set");
 //!
 //!     let years_response = YearQuery::new()
 //!         .execute(&client)
@@ -164,9 +146,7 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let api_key = std::env::var("KODIK_API_KEY").expect("KODIK_API_KEY is not set");
-//!
-//!     let client = Client::new(api_key);
+//!     let client = Client::from_env().expect("KODIK_API_KEY is not set");
 //!
 //!     let qualities_response = QualityQuery::new()
 //!         .execute(&client)
@@ -176,6 +156,24 @@
 //!     println!("qualities response = {qualities_response:#?}");
 //! }
 //! ```
+//!
+//! # ⚙️ Runtimes
+//!
+//! The queries, the streams and the custom transports are plain futures, so they don't depend on an async runtime themselves. Only the default transport needs Tokio, since reqwest runs on the Tokio reactor.
+//!
+//! The delays of retries and of the rate limit don't depend on the runtime. To use the default transport with async-std, enable its `tokio1` feature. With smol, wrap the futures in `async_compat::Compat`. With a custom [`transport::Transport`], such as `MockTransport` behind the `test-util` feature, the whole crate runs on any executor.
+//!
+//! # 📊 Metrics
+//!
+//! With the `metrics` feature enabled, requests are recorded through the [metrics](https://docs.rs/metrics) facade, so any exporter such as Prometheus can be installed by the application:
+//!
+//! | Name | Type | Labels |
+//! | --- | --- | --- |
+//! | `kodik_api_requests_total` | counter | `endpoint`, `outcome` |
+//! | `kodik_api_request_duration_seconds` | histogram | `endpoint`, `outcome` |
+//! | `kodik_api_stream_pages_total` | counter | `endpoint`, `outcome` |
+//!
+//! `endpoint` is the path of the request, for example `/search` or `/list`. For requests `outcome` is one of `success`, `kodik_error`, `http_error` or `decode_error`, for stream pages it is one of `success`, `kodik_error` or `error`.
 
 /// Module containing the [`client::Client`] struct.
 pub mod client;
@@ -204,6 +202,15 @@ pub mod genres;
 /// Module representing the [list qualities] structures.
 pub mod qualities;
 
+/// Module containing the [`reference_data::ReferenceData`] snapshot of translations, genres, countries, years and qualities.
+pub mod reference_data;
+
+/// Module containing the [`server_info::ServerInfo`] about the API server.
+pub mod server_info;
+
+/// Module containing the [`transport::Transport`] the requests are sent with, and the `MockTransport` behind the `test-util` feature.
+pub mod transport;
+
 /// Module representing the [types] structures.
 pub mod types;
 
@@ -212,4 +219,7 @@ pub mod unify_seasons;
 
 pub use client::*;
 
+mod metrics;
+mod rate_limiter;
+mod single_flight;
 mod util;
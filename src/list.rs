@@ -1,27 +1,160 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    str::FromStr,
+    task::Poll,
+};
+
 use async_fn_stream::try_fn_stream;
-use futures_util::{pin_mut, Stream, StreamExt};
-use serde::{Deserialize, Serialize};
+use futures_util::{
+    future::{self, Either},
+    pin_mut, stream, Future, Stream, StreamExt,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::value::RawValue;
+use tokio::sync::mpsc;
 
 use crate::{
+    client::truncate_body,
     error::Error,
+    metrics,
     types::{
-        AllStatus, AnimeKind, AnimeStatus, DramaStatus, MaterialDataField, MppaRating, Release,
-        ReleaseType, TranslationType,
+        AllStatus, AnimeKind, AnimeStatus, Category, CountryCode, DramaStatus, DurationRange,
+        IntoQueryList, MaterialDataField, MppaRating, ProcessingTime, Range, Release,
+        ReleaseQuality, ReleaseType, TranslationType, Year,
+    },
+    util::{
+        deserialize_number_or_string, missing_query_parts, range_query_list,
+        reject_commas_in_lists, serialize_into_query_parts, ResponseUnion,
     },
-    util::serialize_into_query_parts,
-    Client,
+    Client, ResponseMeta,
 };
 
 /// A struct containing releases results and other information about the releases
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ListResponse {
     pub time: String,
+    #[serde(deserialize_with = "deserialize_number_or_string")]
     pub total: i32,
     pub prev_page: Option<String>,
     pub next_page: Option<String>,
     pub results: Vec<Release>,
 }
 
+impl ProcessingTime for ListResponse {
+    fn time(&self) -> &str {
+        &self.time
+    }
+}
+
+impl ListResponse {
+    /// Drops the releases with the given IDs from the results
+    ///
+    /// IDs are matched exactly, including the `movie-` or `serial-` prefix. Every translation of a title is a separate release with its own ID, so other translations of an excluded release are kept. `total` is left as returned by the API.
+    pub fn exclude_ids(&mut self, ids: &[&str]) {
+        self.results
+            .retain(|release| !ids.contains(&release.id.as_str()));
+    }
+}
+
+/// Aggregated counts over the releases of a query
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogStats {
+    /// Number of counted releases
+    pub total: usize,
+
+    pub by_release_type: HashMap<ReleaseType, usize>,
+
+    pub by_year: BTreeMap<i32, usize>,
+
+    pub by_quality: HashMap<ReleaseQuality, usize>,
+
+    pub by_translation_type: HashMap<TranslationType, usize>,
+}
+
+impl CatalogStats {
+    /// Counts the release
+    pub fn add(&mut self, release: &Release) {
+        self.total += 1;
+
+        *self
+            .by_release_type
+            .entry(release.release_type.clone())
+            .or_default() += 1;
+        *self.by_year.entry(release.year).or_default() += 1;
+        *self.by_quality.entry(release.quality.clone()).or_default() += 1;
+        *self
+            .by_translation_type
+            .entry(release.translation.translation_type.clone())
+            .or_default() += 1;
+    }
+}
+
+/// Progress of paging through a query, which can be saved along with a checkpoint of the paging
+///
+/// Kept up to date by [`ListQuery::stream_from_with_progress`], or updated with every page by hand.
+///
+/// ```
+/// use kodik_api::list::ListProgress;
+///
+/// // Resumed after a restart with the progress from the checkpoint, so it doesn't start from zero
+/// let progress = ListProgress::resume(200, Some(1000));
+///
+/// assert_eq!(progress.fraction(), Some(0.2));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListProgress {
+    /// Number of fetched releases
+    pub fetched: usize,
+
+    /// Total number of releases, if known
+    pub total: Option<i32>,
+}
+
+impl ListProgress {
+    /// Constructs a new `ListProgress` with nothing fetched and an unknown total
+    pub fn new() -> ListProgress {
+        ListProgress::default()
+    }
+
+    /// Constructs a `ListProgress` for resumed paging with a known number of fetched releases and total
+    ///
+    /// The total is still refreshed from the pages fetched afterwards, since the catalog may have changed in the meantime.
+    pub fn resume(fetched: usize, total: Option<i32>) -> ListProgress {
+        ListProgress { fetched, total }
+    }
+
+    /// Counts the releases of a fetched page and takes its total
+    pub fn update(&mut self, response: &ListResponse) {
+        self.fetched += response.results.len();
+        self.total = Some(response.total);
+    }
+
+    /// Returns the fetched part of the total from `0.0` to `1.0`, or `None` if the total is unknown
+    pub fn fraction(&self) -> Option<f64> {
+        let total = self.total?;
+
+        if total <= 0 {
+            return Some(1.0);
+        }
+
+        Some((self.fetched as f64 / f64::from(total)).min(1.0))
+    }
+}
+
+/// Releases collected across the pages of a query by [`ListQuery::collect_all`]
+#[derive(Debug, Clone, Default)]
+pub struct CollectedReleases {
+    pub results: Vec<Release>,
+
+    /// Total number of releases, taken from the first page
+    pub total: i32,
+
+    /// Number of fetched pages
+    pub pages: usize,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(untagged)]
 enum ListResponseUnion {
@@ -29,12 +162,177 @@ enum ListResponseUnion {
     Error { error: String },
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl ResponseUnion for ListResponseUnion {
+    fn is_error(&self) -> bool {
+        matches!(self, ListResponseUnion::Error { .. })
+    }
+}
+
+/// Same as `ListResponseUnion`, but the releases are kept as raw JSON to be parsed one at a time
+///
+/// Not an untagged enum, since raw values can't be deserialized through one, the fields are matched in `TryFrom` instead.
+#[derive(Deserialize, Debug)]
+#[serde(try_from = "RawListResponseFields")]
+enum RawListResponseUnion {
+    Result {
+        next_page: Option<String>,
+        results: Vec<Box<RawValue>>,
+    },
+    Error {
+        error: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct RawListResponseFields {
+    error: Option<String>,
+    next_page: Option<String>,
+    results: Option<Vec<Box<RawValue>>>,
+}
+
+impl TryFrom<RawListResponseFields> for RawListResponseUnion {
+    type Error = &'static str;
+
+    fn try_from(fields: RawListResponseFields) -> Result<Self, &'static str> {
+        match (fields.error, fields.results) {
+            (Some(error), _) => Ok(RawListResponseUnion::Error { error }),
+            (None, Some(results)) => Ok(RawListResponseUnion::Result {
+                next_page: fields.next_page,
+                results,
+            }),
+            (None, None) => Err("missing field `results`"),
+        }
+    }
+}
+
+impl ResponseUnion for RawListResponseUnion {
+    fn is_error(&self) -> bool {
+        matches!(self, RawListResponseUnion::Error { .. })
+    }
+}
+
+/// Response of a page of a paged endpoint, which links to the next page
+pub(crate) trait PageResponseUnion: DeserializeOwned + ResponseUnion {
+    type Page;
+
+    /// Returns the page and the link to the next page
+    fn into_page(self) -> Result<(Self::Page, Option<String>), Error>;
+}
+
+impl PageResponseUnion for ListResponseUnion {
+    type Page = ListResponse;
+
+    fn into_page(self) -> Result<(ListResponse, Option<String>), Error> {
+        match self {
+            ListResponseUnion::Result(result) => {
+                let next_page = result.next_page.clone();
+
+                Ok((result, next_page))
+            }
+            ListResponseUnion::Error { error } => Err(Error::KodikError(error)),
+        }
+    }
+}
+
+/// Fetches a page of a paged endpoint, the first one from `path` or the following one from its `next_page` link
+///
+/// Parameters that are missing from the `next_page` link are re-sent with it, so that every page is fetched with the same parameters.
+pub(crate) async fn fetch_page<U: PageResponseUnion>(
+    client: &Client,
+    path: &str,
+    payload: &[(String, String)],
+    accept_language: Option<&str>,
+    next_page: Option<&str>,
+) -> Result<(U::Page, Option<String>), Error> {
+    let request_builder = match next_page {
+        Some(url) => client
+            .init_localized_post_request(url, accept_language)
+            .query(&missing_query_parts(url, payload)),
+        None => client
+            .init_localized_post_request(path, accept_language)
+            .query(payload),
+    };
+
+    let result = client.send_request::<U>(request_builder).await;
+
+    metrics::record_stream_page(
+        path,
+        match &result {
+            Ok(result) if result.is_error() => "kodik_error",
+            Ok(_) => "success",
+            Err(_) => "error",
+        },
+    );
+
+    result.and_then(U::into_page)
+}
+
+/// Streams the pages of a paged endpoint, starting from the `next_page` link or from the first page if it is `None`
+///
+/// The error of a page is yielded and the page is retried.
+pub(crate) fn stream_pages_from<U: PageResponseUnion>(
+    client: &Client,
+    path: &'static str,
+    payload: Result<Vec<(String, String)>, Error>,
+    accept_language: Option<String>,
+    next_page: Option<String>,
+) -> impl Stream<Item = Result<U::Page, Error>> {
+    let client = client.clone();
+
+    try_fn_stream(|emitter| async move {
+        let mut next_page = next_page;
+        let payload = payload?;
+
+        loop {
+            let result = fetch_page::<U>(
+                &client,
+                path,
+                &payload,
+                accept_language.as_deref(),
+                next_page.as_deref(),
+            )
+            .await;
+
+            match result {
+                Ok((page, page_next_page)) => {
+                    next_page = page_next_page;
+
+                    emitter.emit(page).await;
+                }
+                Err(err) => {
+                    emitter.emit_err(err).await;
+
+                    continue;
+                }
+            };
+
+            if next_page.is_none() {
+                break;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+impl PageResponseUnion for RawListResponseUnion {
+    type Page = Vec<Box<RawValue>>;
+
+    fn into_page(self) -> Result<(Vec<Box<RawValue>>, Option<String>), Error> {
+        match self {
+            RawListResponseUnion::Result { next_page, results } => Ok((results, next_page)),
+            RawListResponseUnion::Error { error } => Err(Error::KodikError(error)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ListSort {
     #[serde(rename = "year")]
     Year,
     #[serde(rename = "created_at")]
     CreatedAt,
+    #[default]
     #[serde(rename = "updated_at")]
     UpdatedAt,
     #[serde(rename = "kinopoisk_rating")]
@@ -45,14 +343,81 @@ pub enum ListSort {
     ShikimoriRating,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl ListSort {
+    /// Returns the value as it is sent to the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListSort::Year => "year",
+            ListSort::CreatedAt => "created_at",
+            ListSort::UpdatedAt => "updated_at",
+            ListSort::KinopoiskRating => "kinopoisk_rating",
+            ListSort::ImdbRating => "imdb_rating",
+            ListSort::ShikimoriRating => "shikimori_rating",
+        }
+    }
+}
+
+impl FromStr for ListSort {
+    type Err = Error;
+
+    /// Parses the value as it is sent to the API, for example `"year"`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "year" => Ok(ListSort::Year),
+            "created_at" => Ok(ListSort::CreatedAt),
+            "updated_at" => Ok(ListSort::UpdatedAt),
+            "kinopoisk_rating" => Ok(ListSort::KinopoiskRating),
+            "imdb_rating" => Ok(ListSort::ImdbRating),
+            "shikimori_rating" => Ok(ListSort::ShikimoriRating),
+            _ => Err(Error::InvalidParameter(format!("invalid sort {value:?}"))),
+        }
+    }
+}
+
+impl fmt::Display for ListSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ListOrder {
     #[serde(rename = "asc")]
     Asc,
+    #[default]
     #[serde(rename = "desc")]
     Desc,
 }
 
+impl ListOrder {
+    /// Returns the value as it is sent to the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListOrder::Asc => "asc",
+            ListOrder::Desc => "desc",
+        }
+    }
+}
+
+impl FromStr for ListOrder {
+    type Err = Error;
+
+    /// Parses the value as it is sent to the API, for example `"asc"`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "asc" => Ok(ListOrder::Asc),
+            "desc" => Ok(ListOrder::Desc),
+            _ => Err(Error::InvalidParameter(format!("invalid order {value:?}"))),
+        }
+    }
+}
+
+impl fmt::Display for ListOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct ListQuery<'a> {
     /// Maximum number of outputs
@@ -69,25 +434,25 @@ pub struct ListQuery<'a> {
 
     /// Maximum number of outputs
     #[serde(skip_serializing_if = "Option::is_none")]
-    types: Option<&'a [ReleaseType]>,
+    types: Option<Cow<'a, [ReleaseType]>>,
 
     ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
     #[serde(skip_serializing_if = "Option::is_none")]
-    year: Option<&'a [u32]>,
+    year: Option<Cow<'a, [Year]>>,
 
     /// Filtering materials by translation ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    translation_id: Option<&'a [u32]>,
+    translation_id: Option<Cow<'a, [u32]>>,
     /// Filter content by translation type. Allows you to output only voice translation or only subtitles
     #[serde(skip_serializing_if = "Option::is_none")]
-    translation_type: Option<&'a [TranslationType]>,
+    translation_type: Option<Cow<'a, [TranslationType]>>,
 
     /// Filtering materials based on the presence of a specific field. Materials that have at least one of the listed fields are shown. In order to show only materials that have all the listed fields
     #[serde(skip_serializing_if = "Option::is_none")]
-    has_field: Option<&'a [MaterialDataField]>,
+    has_field: Option<Cow<'a, [MaterialDataField]>>,
     /// Filtering materials based on the presence of a specific field. Materials that have all the listed fields are shown
     #[serde(skip_serializing_if = "Option::is_none")]
-    has_field_and: Option<&'a [MaterialDataField]>,
+    has_field_and: Option<Cow<'a, [MaterialDataField]>>,
 
     /// Filtering materials by camrip parameter. If you specify false, only materials with a quality picture will be output. If you don't specify this parameter, all materials will be displayed
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -102,7 +467,7 @@ pub struct ListQuery<'a> {
 
     /// With this option you can specify which season you are interested in. This way, only shows that have that season will appear in the search results. Passing this parameter also automatically enables the with_seasons parameter
     #[serde(skip_serializing_if = "Option::is_none")]
-    season: Option<&'a [u32]>,
+    season: Option<Cow<'a, [u32]>>,
 
     /// If you specify true, the seasons field will be added to each series (even if with_seasons is not specified or specified as false) and the episodes field with the episodes of that season will be added to each season. If the with_episodes parameter is used, the series numbers will correspond to the normal series references. If you use the with_episodes_data parameter, episode objects will be assigned to the episode numbers, where the link will be available via the link parameter, the episode name (if any) via the title parameter, and the frames via screenshots
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -115,12 +480,19 @@ pub struct ListQuery<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     with_page_links: Option<bool>,
 
+    /// If you specify false, frames are not added to the materials, which reduces the size of the response. The `screenshots` of the releases are empty then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    with_screenshots: Option<bool>,
+    /// If you specify false, frames are not added to the episode objects of `with_episodes_data`. The `screenshots` of the episodes are empty then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    with_episode_screenshots: Option<bool>,
+
     /// Filters materials by country in which they should not be blocked. The country codes are specified separated by commas
     #[serde(skip_serializing_if = "Option::is_none")]
-    not_blocked_in: Option<&'a [&'a str]>,
+    not_blocked_in: Option<Cow<'a, [CountryCode]>>,
     /// A simpler analog of the previous parameter. Our server itself checks which country the current request comes from and doesn't display those materials that are blocked for that country. This parameter can be useful if the API is called on your site
     #[serde(skip_serializing_if = "Option::is_none")]
-    not_blocked_for_me: Option<&'a [&'a str]>,
+    not_blocked_for_me: Option<bool>,
 
     /// If you specify true, the material_data field will be added to each movie/series with information from Kinopoisk and Shikimori
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -128,95 +500,107 @@ pub struct ListQuery<'a> {
 
     /// Filtering materials by country. You can specify a single value or multiple values, separated by commas (then materials with at least one of the listed countries will be displayed). The parameter is case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    countries: Option<&'a [&'a str]>,
+    countries: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    genres: Option<&'a [&'a str]>,
+    genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_genres: Option<&'a [&'a str]>,
+    anime_genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    drama_genres: Option<&'a [&'a str]>,
+    drama_genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    all_genres: Option<&'a [&'a str]>,
+    all_genres: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by duration (in minutes). You can specify either a single value to search for the exact duration, or an interval.
     #[serde(skip_serializing_if = "Option::is_none")]
-    duration: Option<&'a [&'a str]>,
+    duration: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    kinopoisk_rating: Option<&'a [&'a str]>,
+    kinopoisk_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    imdb_rating: Option<&'a [&'a str]>,
+    imdb_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    shikimori_rating: Option<&'a [&'a str]>,
+    shikimori_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    mydramalist_rating: Option<&'a [&'a str]>,
+    mydramalist_rating: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    actors: Option<&'a [&'a str]>,
+    actors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    directors: Option<&'a [&'a str]>,
+    directors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    producers: Option<&'a [&'a str]>,
+    producers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    writers: Option<&'a [&'a str]>,
+    writers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    composers: Option<&'a [&'a str]>,
+    composers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    editors: Option<&'a [&'a str]>,
+    editors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    designers: Option<&'a [&'a str]>,
+    designers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    operators: Option<&'a [&'a str]>,
+    operators: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by age rating. You can specify a single value or multiple values, separated by commas. The parameter is case-insensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    rating_mpaa: Option<&'a [MppaRating]>,
+    rating_mpaa: Option<Cow<'a, [MppaRating]>>,
 
     /// Filter content by the minimum age from which it can be viewed. You can specify either a single value or a range of values
     #[serde(skip_serializing_if = "Option::is_none")]
-    minimal_age: Option<&'a [&'a str]>,
+    minimal_age: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by anime type. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_kind: Option<&'a [AnimeKind]>,
+    anime_kind: Option<Cow<'a, [AnimeKind]>>,
 
     /// Filters materials by MyDramaList tags. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    mydramalist_tags: Option<&'a [&'a str]>,
+    mydramalist_tags: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_status: Option<&'a [AnimeStatus]>,
+    anime_status: Option<Cow<'a, [AnimeStatus]>>,
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    drama_status: Option<&'a [DramaStatus]>,
+    drama_status: Option<Cow<'a, [DramaStatus]>>,
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    all_status: Option<&'a [AllStatus]>,
+    all_status: Option<Cow<'a, [AllStatus]>>,
 
     /// Filtering materials by anime studio. You can specify either one value or several values separated by commas (then materials with at least one of the listed studios will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_studios: Option<&'a [&'a str]>,
+    anime_studios: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by license owner. You can specify a single value or several values separated by commas (then materials that have at least one of the listed owners will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_licensed_by: Option<&'a [&'a str]>,
+    anime_licensed_by: Option<Cow<'a, [Cow<'a, str>]>>,
+
+    /// Value of the `Accept-Language` header for this query, overrides the client one
+    #[serde(skip)]
+    accept_language: Option<&'a str>,
+
+    /// Skip releases that were already emitted by [`ListQuery::stream_releases`]
+    #[serde(skip)]
+    deduplicate: bool,
+
+    /// Client bound by [`Client::list`], used by [`ListQuery::send`]
+    #[serde(skip)]
+    client: Option<Client>,
 }
 
 impl<'a> ListQuery<'a> {
@@ -238,6 +622,8 @@ impl<'a> ListQuery<'a> {
             with_episodes: None,
             with_episodes_data: None,
             with_page_links: None,
+            with_screenshots: None,
+            with_episode_screenshots: None,
             not_blocked_in: None,
             not_blocked_for_me: None,
             with_material_data: None,
@@ -268,6 +654,9 @@ impl<'a> ListQuery<'a> {
             all_status: None,
             anime_studios: None,
             anime_licensed_by: None,
+            accept_language: None,
+            deduplicate: false,
+            client: None,
         }
     }
 
@@ -290,49 +679,61 @@ impl<'a> ListQuery<'a> {
     }
 
     /// Maximum number of outputs
-    pub fn with_types<'b>(&'b mut self, types: &'a [ReleaseType]) -> &'b mut ListQuery<'a> {
-        self.types = Some(types);
+    pub fn with_types<'b>(
+        &'b mut self,
+        types: impl IntoQueryList<'a, ReleaseType>,
+    ) -> &'b mut ListQuery<'a> {
+        self.types = Some(ReleaseType::known_only(types.into_query_list()));
         self
     }
 
-    ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
+    /// Filter materials by categories, expanded to the underlying release types. Replaces the types set by `with_types`
+    pub fn with_category<'b>(&'b mut self, categories: &[Category]) -> &'b mut ListQuery<'a> {
+        self.types = Some(Cow::Owned(Category::release_types_of(categories)));
+        self
+    }
 
-    pub fn with_year<'b>(&'b mut self, year: &'a [u32]) -> &'b mut ListQuery<'a> {
-        self.year = Some(year);
+    ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
+    ///
+    pub fn with_year<'b>(
+        &'b mut self,
+        year: impl IntoQueryList<'a, Year>,
+    ) -> &'b mut ListQuery<'a> {
+        self.year = Some(year.into_query_list());
         self
     }
 
     /// Filtering materials by translation ID
     pub fn with_translation_id<'b>(
         &'b mut self,
-        translation_id: &'a [u32],
+        translation_id: impl IntoQueryList<'a, u32>,
     ) -> &'b mut ListQuery<'a> {
-        self.translation_id = Some(translation_id);
+        self.translation_id = Some(translation_id.into_query_list());
         self
     }
     /// Filter content by translation type. Allows you to output only voice translation or only subtitles
     pub fn with_translation_type<'b>(
         &'b mut self,
-        translation_type: &'a [TranslationType],
+        translation_type: impl IntoQueryList<'a, TranslationType>,
     ) -> &'b mut ListQuery<'a> {
-        self.translation_type = Some(translation_type);
+        self.translation_type = Some(translation_type.into_query_list());
         self
     }
 
     /// Filtering materials based on the presence of a specific field. Materials that have at least one of the listed fields are shown. In order to show only materials that have all the listed fields
     pub fn with_has_field<'b>(
         &'b mut self,
-        has_field: &'a [MaterialDataField],
+        has_field: impl IntoQueryList<'a, MaterialDataField>,
     ) -> &'b mut ListQuery<'a> {
-        self.has_field = Some(has_field);
+        self.has_field = Some(has_field.into_query_list());
         self
     }
     /// Filtering materials based on the presence of a specific field. Materials that have all the listed fields are shown
     pub fn with_has_field_and<'b>(
         &'b mut self,
-        has_field: &'a [MaterialDataField],
+        has_field: impl IntoQueryList<'a, MaterialDataField>,
     ) -> &'b mut ListQuery<'a> {
-        self.has_field_and = Some(has_field);
+        self.has_field_and = Some(has_field.into_query_list());
         self
     }
 
@@ -354,8 +755,11 @@ impl<'a> ListQuery<'a> {
     }
 
     /// With this option you can specify which season you are interested in. This way, only shows that have that season will appear in the search results. Passing this parameter also automatically enables the with_seasons parameter
-    pub fn with_season<'b>(&'b mut self, season: &'a [u32]) -> &'b mut ListQuery<'a> {
-        self.season = Some(season);
+    pub fn with_season<'b>(
+        &'b mut self,
+        season: impl IntoQueryList<'a, u32>,
+    ) -> &'b mut ListQuery<'a> {
+        self.season = Some(season.into_query_list());
         self
     }
 
@@ -376,260 +780,778 @@ impl<'a> ListQuery<'a> {
         self
     }
 
+    /// If you specify false, frames are not added to the materials, which reduces the size of the response. The `screenshots` of the releases are empty then
+    pub fn with_screenshots<'b>(&'b mut self, with_screenshots: bool) -> &'b mut ListQuery<'a> {
+        self.with_screenshots = Some(with_screenshots);
+        self
+    }
+    /// If you specify false, frames are not added to the episode objects of `with_episodes_data`. The `screenshots` of the episodes are empty then
+    pub fn with_episode_screenshots<'b>(
+        &'b mut self,
+        with_episode_screenshots: bool,
+    ) -> &'b mut ListQuery<'a> {
+        self.with_episode_screenshots = Some(with_episode_screenshots);
+        self
+    }
+
     /// Filters materials by country in which they should not be blocked. The country codes are specified separated by commas
     pub fn with_not_blocked_in<'b>(
         &'b mut self,
-        not_blocked_in: &'a [&'a str],
+        not_blocked_in: impl IntoQueryList<'a, CountryCode>,
     ) -> &'b mut ListQuery<'a> {
-        self.not_blocked_in = Some(not_blocked_in);
+        self.not_blocked_in = Some(not_blocked_in.into_query_list());
         self
     }
     /// A simpler analog of the previous parameter. Our server itself checks which country the current request comes from and doesn't display those materials that are blocked for that country. This parameter can be useful if the API is called on your site
     pub fn with_not_blocked_for_me<'b>(
         &'b mut self,
-        not_blocked_for_me: &'a [&'a str],
+        not_blocked_for_me: bool,
     ) -> &'b mut ListQuery<'a> {
         self.not_blocked_for_me = Some(not_blocked_for_me);
         self
     }
     /// If you specify true, the material_data field will be added to each movie/series with information from Kinopoisk and Shikimori
+    ///
+    /// If not set, it is enabled automatically when a filter by the material data is used: countries, genres, duration, ratings, personas, `rating_mpaa`, `minimal_age`, `anime_kind`, `mydramalist_tags`, statuses, `anime_studios` or `anime_licensed_by`. Set it to false explicitly to opt out.
     pub fn with_material_data<'b>(&'b mut self, with_material_data: bool) -> &'b mut ListQuery<'a> {
         self.with_material_data = Some(with_material_data);
         self
     }
 
     /// Filtering materials by country. You can specify a single value or multiple values, separated by commas (then materials with at least one of the listed countries will be displayed). The parameter is case sensitive
-    pub fn with_countries<'b>(&'b mut self, countries: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.countries = Some(countries);
+    pub fn with_countries<'b>(
+        &'b mut self,
+        countries: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.countries = Some(countries.into_query_list());
         self
     }
 
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
-    pub fn with_genres<'b>(&'b mut self, genres: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.genres = Some(genres);
+    pub fn with_genres<'b>(
+        &'b mut self,
+        genres: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.genres = Some(genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     pub fn with_anime_genres<'b>(
         &'b mut self,
-        anime_genres: &'a [&'a str],
+        anime_genres: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut ListQuery<'a> {
-        self.anime_genres = Some(anime_genres);
+        self.anime_genres = Some(anime_genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     pub fn with_drama_genres<'b>(
         &'b mut self,
-        drama_genres: &'a [&'a str],
+        drama_genres: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut ListQuery<'a> {
-        self.drama_genres = Some(drama_genres);
+        self.drama_genres = Some(drama_genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
-    pub fn with_all_genres<'b>(&'b mut self, all_genres: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.all_genres = Some(all_genres);
+    pub fn with_all_genres<'b>(
+        &'b mut self,
+        all_genres: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.all_genres = Some(all_genres.into_query_list());
         self
     }
 
     /// Filtering by duration (in minutes). You can specify either a single value to search for the exact duration, or an interval.
-    pub fn with_duration<'b>(&'b mut self, duration: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.duration = Some(duration);
+    pub fn with_duration<'b>(
+        &'b mut self,
+        duration: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.duration = Some(duration.into_query_list());
+        self
+    }
+    /// Same as `with_duration`, but with a typed exact value or interval
+    pub fn with_duration_range<'b>(&'b mut self, duration: DurationRange) -> &'b mut ListQuery<'a> {
+        self.duration = Some(range_query_list(duration));
         self
     }
 
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_kinopoisk_rating<'b>(
         &'b mut self,
-        kinopoisk_rating: &'a [&'a str],
+        kinopoisk_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut ListQuery<'a> {
-        self.kinopoisk_rating = Some(kinopoisk_rating);
+        self.kinopoisk_rating = Some(kinopoisk_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
-    pub fn with_imdb_rating<'b>(&'b mut self, imdb_rating: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.imdb_rating = Some(imdb_rating);
+    pub fn with_imdb_rating<'b>(
+        &'b mut self,
+        imdb_rating: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.imdb_rating = Some(imdb_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_shikimori_rating<'b>(
         &'b mut self,
-        shikimori_rating: &'a [&'a str],
+        shikimori_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut ListQuery<'a> {
-        self.shikimori_rating = Some(shikimori_rating);
+        self.shikimori_rating = Some(shikimori_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_mydramalist_rating<'b>(
         &'b mut self,
-        mydramalist_rating: &'a [&'a str],
+        mydramalist_rating: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.mydramalist_rating = Some(mydramalist_rating.into_query_list());
+        self
+    }
+    /// Same as `with_kinopoisk_rating`, but with a typed exact value or interval
+    pub fn with_kinopoisk_rating_range<'b>(
+        &'b mut self,
+        kinopoisk_rating: Range,
+    ) -> &'b mut ListQuery<'a> {
+        self.kinopoisk_rating = Some(range_query_list(kinopoisk_rating));
+        self
+    }
+    /// Same as `with_imdb_rating`, but with a typed exact value or interval
+    pub fn with_imdb_rating_range<'b>(&'b mut self, imdb_rating: Range) -> &'b mut ListQuery<'a> {
+        self.imdb_rating = Some(range_query_list(imdb_rating));
+        self
+    }
+    /// Same as `with_shikimori_rating`, but with a typed exact value or interval
+    pub fn with_shikimori_rating_range<'b>(
+        &'b mut self,
+        shikimori_rating: Range,
     ) -> &'b mut ListQuery<'a> {
-        self.mydramalist_rating = Some(mydramalist_rating);
+        self.shikimori_rating = Some(range_query_list(shikimori_rating));
+        self
+    }
+    /// Same as `with_mydramalist_rating`, but with a typed exact value or interval
+    pub fn with_mydramalist_rating_range<'b>(
+        &'b mut self,
+        mydramalist_rating: Range,
+    ) -> &'b mut ListQuery<'a> {
+        self.mydramalist_rating = Some(range_query_list(mydramalist_rating));
         self
     }
 
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_actors<'b>(&'b mut self, actors: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.actors = Some(actors);
+    pub fn with_actors<'b>(
+        &'b mut self,
+        actors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.actors = Some(actors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_directors<'b>(&'b mut self, directors: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.directors = Some(directors);
+    pub fn with_directors<'b>(
+        &'b mut self,
+        directors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.directors = Some(directors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_producers<'b>(&'b mut self, producers: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.producers = Some(producers);
+    pub fn with_producers<'b>(
+        &'b mut self,
+        producers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.producers = Some(producers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_writers<'b>(&'b mut self, writers: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.writers = Some(writers);
+    pub fn with_writers<'b>(
+        &'b mut self,
+        writers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.writers = Some(writers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_composers<'b>(&'b mut self, composers: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.composers = Some(composers);
+    pub fn with_composers<'b>(
+        &'b mut self,
+        composers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.composers = Some(composers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_editors<'b>(&'b mut self, editors: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.editors = Some(editors);
+    pub fn with_editors<'b>(
+        &'b mut self,
+        editors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.editors = Some(editors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_designers<'b>(&'b mut self, designers: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.designers = Some(designers);
+    pub fn with_designers<'b>(
+        &'b mut self,
+        designers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.designers = Some(designers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_operators<'b>(&'b mut self, operators: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.operators = Some(operators);
+    pub fn with_operators<'b>(
+        &'b mut self,
+        operators: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.operators = Some(operators.into_query_list());
         self
     }
 
     /// Filtering materials by age rating. You can specify a single value or multiple values, separated by commas. The parameter is case-insensitive
     pub fn with_rating_mpaa<'b>(
         &'b mut self,
-        rating_mpaa: &'a [MppaRating],
+        rating_mpaa: impl IntoQueryList<'a, MppaRating>,
     ) -> &'b mut ListQuery<'a> {
-        self.rating_mpaa = Some(rating_mpaa);
+        self.rating_mpaa = Some(rating_mpaa.into_query_list());
         self
     }
 
     /// Filter content by the minimum age from which it can be viewed. You can specify either a single value or a range of values
-    pub fn with_minimal_age<'b>(&'b mut self, minimal_age: &'a [&'a str]) -> &'b mut ListQuery<'a> {
-        self.minimal_age = Some(minimal_age);
+    pub fn with_minimal_age<'b>(
+        &'b mut self,
+        minimal_age: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.minimal_age = Some(minimal_age.into_query_list());
         self
     }
 
     /// Filtering materials by anime type. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
-    pub fn with_anime_kind<'b>(&'b mut self, anime_kind: &'a [AnimeKind]) -> &'b mut ListQuery<'a> {
-        self.anime_kind = Some(anime_kind);
+    pub fn with_anime_kind<'b>(
+        &'b mut self,
+        anime_kind: impl IntoQueryList<'a, AnimeKind>,
+    ) -> &'b mut ListQuery<'a> {
+        self.anime_kind = Some(anime_kind.into_query_list());
         self
     }
 
     /// Filters materials by MyDramaList tags. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     pub fn with_mydramalist_tags<'b>(
         &'b mut self,
-        mydramalist_tags: &'a [&'a str],
+        mydramalist_tags: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut ListQuery<'a> {
-        self.mydramalist_tags = Some(mydramalist_tags);
+        self.mydramalist_tags = Some(mydramalist_tags.into_query_list());
         self
     }
 
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_anime_status<'b>(
         &'b mut self,
-        anime_status: &'a [AnimeStatus],
+        anime_status: impl IntoQueryList<'a, AnimeStatus>,
     ) -> &'b mut ListQuery<'a> {
-        self.anime_status = Some(anime_status);
+        self.anime_status = Some(anime_status.into_query_list());
         self
     }
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_drama_status<'b>(
         &'b mut self,
-        drama_status: &'a [DramaStatus],
+        drama_status: impl IntoQueryList<'a, DramaStatus>,
     ) -> &'b mut ListQuery<'a> {
-        self.drama_status = Some(drama_status);
+        self.drama_status = Some(drama_status.into_query_list());
         self
     }
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
-    pub fn with_all_status<'b>(&'b mut self, all_status: &'a [AllStatus]) -> &'b mut ListQuery<'a> {
-        self.all_status = Some(all_status);
+    pub fn with_all_status<'b>(
+        &'b mut self,
+        all_status: impl IntoQueryList<'a, AllStatus>,
+    ) -> &'b mut ListQuery<'a> {
+        self.all_status = Some(all_status.into_query_list());
         self
     }
 
     /// Filtering materials by anime studio. You can specify either one value or several values separated by commas (then materials with at least one of the listed studios will be displayed)
     pub fn with_anime_studios<'b>(
         &'b mut self,
-        anime_studios: &'a [&'a str],
+        anime_studios: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut ListQuery<'a> {
-        self.anime_studios = Some(anime_studios);
+        self.anime_studios = Some(anime_studios.into_query_list());
         self
     }
     /// Filtering materials by license owner. You can specify a single value or several values separated by commas (then materials that have at least one of the listed owners will be displayed)
     pub fn with_anime_licensed_by<'b>(
         &'b mut self,
-        anime_licensed_by: &'a [&'a str],
+        anime_licensed_by: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut ListQuery<'a> {
+        self.anime_licensed_by = Some(anime_licensed_by.into_query_list());
+        self
+    }
+
+    /// Value of the `Accept-Language` header for this query, overrides the one set in [`crate::ClientBuilder::accept_language`]
+    pub fn with_accept_language<'b>(
+        &'b mut self,
+        accept_language: &'a str,
     ) -> &'b mut ListQuery<'a> {
-        self.anime_licensed_by = Some(anime_licensed_by);
+        self.accept_language = Some(accept_language);
         self
     }
 
+    /// Skip releases that were already emitted by [`ListQuery::stream_releases`]
+    ///
+    /// Pages are fetched one by one, so on a frequently updated catalog (for example sorted by `updated_at`) the same release can shift to the next page between requests. The IDs of all emitted releases are kept in memory for the lifetime of the stream, each stream tracks its own IDs.
+    pub fn with_deduplication<'b>(&'b mut self, deduplicate: bool) -> &'b mut ListQuery<'a> {
+        self.deduplicate = deduplicate;
+        self
+    }
+
+    /// Checks the query for combinations of parameters the API doesn't accept, without sending a request
+    ///
+    /// Called by `execute` and `stream` first. Rejected with [`Error::InvalidQuery`] are:
+    /// - `limit` of `0`
+    /// - `with_types` without known release types
+    ///
+    /// A list value containing a comma is rejected with [`Error::InvalidParameter`].
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.limit == Some(0) {
+            return Err(Error::InvalidQuery(
+                "`limit` must be greater than 0".to_owned(),
+            ));
+        }
+
+        ReleaseType::validate_query_types(self.types.as_deref())?;
+
+        reject_commas_in_lists(self)?;
+
+        Ok(())
+    }
+
+    /// Serializes the query, enabling `with_material_data` if a filter by the material data is used and it is not set explicitly
+    fn query_parts(&self) -> Result<Vec<(String, String)>, Error> {
+        if self.with_material_data.is_some() || !self.has_material_data_filters() {
+            return serialize_into_query_parts(self);
+        }
+
+        let mut query = self.clone();
+
+        query.with_material_data(true);
+
+        serialize_into_query_parts(&query)
+    }
+
+    fn has_material_data_filters(&self) -> bool {
+        self.countries.is_some()
+            || self.genres.is_some()
+            || self.anime_genres.is_some()
+            || self.drama_genres.is_some()
+            || self.all_genres.is_some()
+            || self.duration.is_some()
+            || self.kinopoisk_rating.is_some()
+            || self.imdb_rating.is_some()
+            || self.shikimori_rating.is_some()
+            || self.mydramalist_rating.is_some()
+            || self.actors.is_some()
+            || self.directors.is_some()
+            || self.producers.is_some()
+            || self.writers.is_some()
+            || self.composers.is_some()
+            || self.editors.is_some()
+            || self.designers.is_some()
+            || self.operators.is_some()
+            || self.rating_mpaa.is_some()
+            || self.minimal_age.is_some()
+            || self.anime_kind.is_some()
+            || self.mydramalist_tags.is_some()
+            || self.anime_status.is_some()
+            || self.drama_status.is_some()
+            || self.all_status.is_some()
+            || self.anime_studios.is_some()
+            || self.anime_licensed_by.is_some()
+    }
+
     /// Execute the query and fetch the results.
-    pub async fn execute<'b>(&'a self, client: &'b Client) -> Result<ListResponse, Error> {
-        let stream = self.stream(client);
+    ///
+    /// If nothing matches the query, an empty response is returned instead of an error.
+    pub async fn execute(&self, client: &Client) -> Result<ListResponse, Error> {
+        self.validate()?;
 
-        pin_mut!(stream);
+        let payload = self.query_parts()?;
 
-        stream
-            .next()
-            .await
-            .ok_or_else(|| Error::KodikError("Empty response".to_owned()))?
+        let request_builder = client
+            .init_localized_post_request("/list", self.accept_language)
+            .query(&payload);
+
+        let result = client
+            .execute_request::<ListResponseUnion>(request_builder)
+            .await?;
+
+        match result {
+            ListResponseUnion::Result(result) => Ok(result),
+            ListResponseUnion::Error { error } => Err(Error::KodikError(error)),
+        }
+    }
+
+    /// Execute the query with the client it was created by, see [`Client::list`]
+    ///
+    /// Fails with [`Error::InvalidQuery`] if the query was created with [`ListQuery::new`], use [`ListQuery::execute`] for it.
+    pub async fn send(&self) -> Result<ListResponse, Error> {
+        let client = self.client.as_ref().ok_or_else(|| {
+            Error::InvalidQuery("the query is not bound to a client, use `execute`".to_owned())
+        })?;
+
+        self.execute(client).await
+    }
+
+    /// Same as `execute`, but also returns the status and the headers of the response
+    ///
+    /// Requests are not coalesced with [`crate::ClientBuilder::single_flight`], since every caller gets the headers of its own response.
+    ///
+    /// ```
+    /// use kodik_api::{list::ListQuery, Client};
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let (response, meta) = ListQuery::new()
+    ///     .execute_raw(&client)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("total = {}", response.total);
+    /// println!("retry after = {:?}", meta.headers.get("retry-after"));
+    /// # }
+    /// ```
+    pub async fn execute_raw(
+        &self,
+        client: &Client,
+    ) -> Result<(ListResponse, ResponseMeta), Error> {
+        self.validate()?;
+
+        let payload = self.query_parts()?;
+
+        let request_builder = client
+            .init_localized_post_request("/list", self.accept_language)
+            .query(&payload);
+
+        let (meta, result) = client
+            .send_request_with_headers::<ListResponseUnion>(request_builder)
+            .await?;
+
+        match result {
+            ListResponseUnion::Result(result) => Ok((result, meta)),
+            ListResponseUnion::Error { error } => Err(Error::KodikError(error)),
+        }
     }
 
     /// Stream the query
+    ///
+    /// Parameters that are missing from the `next_page` link are re-sent with it, so that every page is fetched with the same parameters.
     pub fn stream(&self, client: &Client) -> impl Stream<Item = Result<ListResponse, Error>> {
-        let client = client.clone();
-        let payload = serialize_into_query_parts(self);
+        self.stream_from(client, None)
+    }
+
+    /// Stream the query starting from a saved `next_page` link
+    ///
+    /// Allows resuming the paging after a restart with the `next_page` of the last processed page. With `None` it is the same as `stream`.
+    ///
+    /// ```
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use kodik_api::{list::ListQuery, Client};
+    ///
+    /// # async fn run(saved_next_page: Option<String>) {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let stream = ListQuery::new().stream_from(&client, saved_next_page);
+    ///
+    /// pin_mut!(stream);
+    ///
+    /// while let Some(Ok(response)) = stream.next().await {
+    ///     // Save `response.next_page` as a checkpoint
+    ///     println!("next page = {:?}", response.next_page);
+    /// }
+    /// # }
+    /// ```
+    pub fn stream_from(
+        &self,
+        client: &Client,
+        next_page: Option<String>,
+    ) -> impl Stream<Item = Result<ListResponse, Error>> {
+        self.stream_pages_from::<ListResponseUnion>(client, next_page)
+    }
+
+    fn stream_pages_from<U: PageResponseUnion>(
+        &self,
+        client: &Client,
+        next_page: Option<String>,
+    ) -> impl Stream<Item = Result<U::Page, Error>> {
+        stream_pages_from::<U>(
+            client,
+            "/list",
+            self.validate().and_then(|()| self.query_parts()),
+            self.accept_language.map(str::to_owned),
+            next_page,
+        )
+    }
+
+    /// Same as `stream_from`, but every page is yielded with the progress updated by it
+    ///
+    /// Resume with the `next_page` and the progress saved from the last processed page, so that the progress doesn't start from zero.
+    ///
+    /// ```
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use kodik_api::{
+    ///     list::{ListProgress, ListQuery},
+    ///     Client,
+    /// };
+    ///
+    /// # async fn run(saved_next_page: Option<String>, saved_progress: ListProgress) {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let stream = ListQuery::new().stream_from_with_progress(&client, saved_next_page, saved_progress);
+    ///
+    /// pin_mut!(stream);
+    ///
+    /// while let Some(Ok((response, progress))) = stream.next().await {
+    ///     // Save `response.next_page` and `progress` as a checkpoint
+    ///     println!("progress = {:?}", progress.fraction());
+    /// }
+    /// # }
+    /// ```
+    pub fn stream_from_with_progress(
+        &self,
+        client: &Client,
+        next_page: Option<String>,
+        mut progress: ListProgress,
+    ) -> impl Stream<Item = Result<(ListResponse, ListProgress), Error>> {
+        self.stream_from(client, next_page).map(move |result| {
+            result.map(|response| {
+                progress.update(&response);
+
+                (response, progress)
+            })
+        })
+    }
+
+    /// Same as `stream`, but ends after `max_pages` pages even if there are more
+    ///
+    /// Guards against crawling the whole catalogue by mistake. Every page request counts towards the budget, including the failed ones, so a persistent error can't make the stream send requests endlessly. The stream ends the same way in both cases: if it stopped due to the budget, the `next_page` of the last fetched page is still set and can be passed to `stream_from` to continue, otherwise it is `None`.
+    ///
+    /// ```
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use kodik_api::{list::ListQuery, Client};
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let stream = ListQuery::new().stream_limited(&client, 10);
+    ///
+    /// pin_mut!(stream);
+    ///
+    /// let mut next_page = None;
+    ///
+    /// while let Some(Ok(response)) = stream.next().await {
+    ///     next_page = response.next_page;
+    /// }
+    ///
+    /// if next_page.is_some() {
+    ///     println!("stopped due to the budget");
+    /// }
+    /// # }
+    /// ```
+    pub fn stream_limited(
+        &self,
+        client: &Client,
+        max_pages: usize,
+    ) -> impl Stream<Item = Result<ListResponse, Error>> {
+        let stream = self.stream(client);
 
         try_fn_stream(|emitter| async move {
-            let mut next_page: Option<String> = None;
-            let payload = payload?;
+            if max_pages == 0 {
+                return Ok(());
+            }
+
+            let mut pages = 0;
+
+            pin_mut!(stream);
+
+            while let Some(response) = stream.next().await {
+                match response {
+                    Ok(response) => emitter.emit(response).await,
+                    Err(err) => emitter.emit_err(err).await,
+                }
+
+                pages += 1;
+
+                if pages == max_pages {
+                    break;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Same as `stream`, but ends as soon as the `shutdown` future completes
+    ///
+    /// Dropping a stream also drops its request in flight, but only the owner of the stream can do that. The `shutdown` signal can come from anywhere, for example a `tokio::sync::oneshot` receiver or `CancellationToken::cancelled_owned` of `tokio-util`. Once it completes, the request in flight is dropped, the page it was fetching is not yielded and no further pages are requested. Pages yielded before that are not affected.
+    ///
+    /// ```
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use kodik_api::{list::ListQuery, Client};
+    /// use tokio::sync::oneshot;
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    ///
+    /// let stream = ListQuery::new().stream_until(&client, async {
+    ///     // Also completes if the sender is dropped
+    ///     let _ = shutdown_rx.await;
+    /// });
+    ///
+    /// pin_mut!(stream);
+    ///
+    /// while let Some(response) = stream.next().await {
+    ///     println!("response = {response:#?}");
+    /// }
+    /// # drop(shutdown_tx);
+    /// # }
+    /// ```
+    pub fn stream_until(
+        &self,
+        client: &Client,
+        shutdown: impl Future<Output = ()>,
+    ) -> impl Stream<Item = Result<ListResponse, Error>> {
+        let stream = self.stream(client);
+
+        try_fn_stream(|emitter| async move {
+            pin_mut!(stream);
+            pin_mut!(shutdown);
 
             loop {
-                let request_builder = if let Some(url) = &next_page {
-                    client.init_post_request(url)
-                } else {
-                    client.init_post_request("/list").query(&payload)
-                };
+                match future::select(stream.next(), shutdown.as_mut()).await {
+                    Either::Left((Some(Ok(response)), _)) => emitter.emit(response).await,
+                    Either::Left((Some(Err(err)), _)) => emitter.emit_err(err).await,
+                    Either::Left((None, _)) | Either::Right(_) => break,
+                }
+            }
 
-                let response = request_builder.send().await.map_err(Error::HttpError);
+            Ok(())
+        })
+    }
 
-                let result = match response {
-                    Ok(response) => response
-                        .json::<ListResponseUnion>()
-                        .await
-                        .map_err(Error::HttpError),
-                    Err(error) => {
-                        emitter.emit_err(error).await;
+    /// Same as `stream`, but requests the next page before the current one is yielded
+    ///
+    /// This is a single-page readahead: since the link to the next page is known only from the previous response, at most one request is in flight and nothing is fetched while the stream is not polled. The request for the next page is sent when a page is handed over, so the HTTP client can receive its response while the consumer awaits something else, but the response is decoded only on the next poll. Pages are yielded in order. No tasks are spawned, so it works on any runtime.
+    ///
+    /// ```
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use kodik_api::{list::ListQuery, Client};
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let stream = ListQuery::new().with_limit(100).stream_readahead(&client);
+    ///
+    /// pin_mut!(stream);
+    ///
+    /// while let Some(response) = stream.next().await {
+    ///     println!("response = {response:#?}");
+    /// }
+    /// # }
+    /// ```
+    pub fn stream_readahead(
+        &self,
+        client: &Client,
+    ) -> impl Stream<Item = Result<ListResponse, Error>> {
+        let mut stream = Box::pin(self.stream(client));
+        let mut next = None;
+        let mut finished = false;
+
+        stream::poll_fn(move |cx| {
+            let response = match next.take() {
+                Some(response) => response,
+                None if finished => return Poll::Ready(None),
+                None => match stream.poll_next_unpin(cx) {
+                    Poll::Ready(Some(response)) => response,
+                    Poll::Ready(None) => return Poll::Ready(None),
+                    Poll::Pending => return Poll::Pending,
+                },
+            };
+
+            // Start the request for the next page before handing this one over
+            match stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(next_response)) => next = Some(next_response),
+                Poll::Ready(None) => finished = true,
+                Poll::Pending => {}
+            }
 
-                        continue;
-                    }
-                };
+            Poll::Ready(Some(response))
+        })
+    }
 
-                match result {
-                    Ok(ListResponseUnion::Result(result)) => {
-                        next_page.clone_from(&result.next_page);
+    /// Stream the query releases one by one across all pages
+    pub fn stream_releases(&self, client: &Client) -> impl Stream<Item = Result<Release, Error>> {
+        let stream = self.stream(client);
+        let deduplicate = self.deduplicate;
 
-                        emitter.emit(result).await;
-                    }
-                    Ok(ListResponseUnion::Error { error }) => {
-                        emitter.emit_err(Error::KodikError(error)).await;
+        try_fn_stream(|emitter| async move {
+            let mut seen_ids = HashSet::new();
 
-                        continue;
+            pin_mut!(stream);
+
+            while let Some(response) = stream.next().await {
+                match response {
+                    Ok(response) => {
+                        for release in response.results {
+                            if deduplicate && !seen_ids.insert(release.id.clone()) {
+                                continue;
+                            }
+
+                            emitter.emit(release).await;
+                        }
                     }
+                    Err(err) => {
+                        emitter.emit_err(err).await;
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Same as `stream_releases`, but each release is parsed only when it is yielded
+    ///
+    /// Reduces the memory on large pages, especially with `with_material_data`: instead of a whole page of parsed releases, only the raw JSON of the page and a single parsed release are kept at a time. The body of a page is still received in full before its first release is yielded.
+    ///
+    /// Errors of a page are yielded and the page is retried like in `stream`. A release which fails to parse is yielded as [`Error::DecodeError`] with its raw JSON and skipped, instead of failing the whole page.
+    ///
+    /// ```
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use kodik_api::{list::ListQuery, Client};
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let stream = ListQuery::new()
+    ///     .with_material_data(true)
+    ///     .stream_releases_lazy(&client);
+    ///
+    /// pin_mut!(stream);
+    ///
+    /// while let Some(release) = stream.next().await {
+    ///     println!("release = {release:#?}");
+    /// }
+    /// # }
+    /// ```
+    pub fn stream_releases_lazy(
+        &self,
+        client: &Client,
+    ) -> impl Stream<Item = Result<Release, Error>> {
+        let pages = self.stream_pages_from::<RawListResponseUnion>(client, None);
+        let deduplicate = self.deduplicate;
+
+        try_fn_stream(|emitter| async move {
+            let mut seen_ids = HashSet::new();
+
+            pin_mut!(pages);
+
+            while let Some(page) = pages.next().await {
+                let raw_releases = match page {
+                    Ok(raw_releases) => raw_releases,
                     Err(err) => {
                         emitter.emit_err(err).await;
 
@@ -637,14 +1559,162 @@ impl<'a> ListQuery<'a> {
                     }
                 };
 
-                if next_page.is_none() {
-                    break;
+                for raw_release in raw_releases {
+                    match serde_json::from_str::<Release>(raw_release.get()) {
+                        Ok(release) => {
+                            if deduplicate && !seen_ids.insert(release.id.clone()) {
+                                continue;
+                            }
+
+                            emitter.emit(release).await;
+                        }
+                        Err(source) => {
+                            emitter
+                                .emit_err(Error::DecodeError {
+                                    source,
+                                    body: truncate_body(raw_release.get().to_owned()),
+                                })
+                                .await;
+                        }
+                    }
                 }
             }
 
             Ok(())
         })
     }
+
+    /// Sends the query releases one by one into the channel
+    ///
+    /// Waits for free capacity when the channel is full, so fetching never runs ahead of the consumer by more than the channel capacity. Errors are sent into the channel as well, and the failed page is retried like in `stream`. Returns once all pages are sent or as soon as the receiver is dropped or closed, without fetching further pages.
+    ///
+    /// ```
+    /// use kodik_api::{list::ListQuery, Client};
+    /// use tokio::sync::mpsc;
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let (tx, mut rx) = mpsc::channel(100);
+    ///
+    /// tokio::spawn(async move {
+    ///     while let Some(release) = rx.recv().await {
+    ///         println!("release = {release:#?}");
+    ///     }
+    /// });
+    ///
+    /// ListQuery::new().stream_into_channel(&client, tx).await;
+    /// # }
+    /// ```
+    pub async fn stream_into_channel(
+        &self,
+        client: &Client,
+        tx: mpsc::Sender<Result<Release, Error>>,
+    ) {
+        let stream = self.stream_releases(client);
+
+        pin_mut!(stream);
+
+        while let Some(release) = stream.next().await {
+            if tx.send(release).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Collects the query releases across all pages
+    ///
+    /// Respects `with_deduplication`. Fails on the first error instead of retrying the page. Along with the releases, returns the total from the first page and the number of fetched pages. Use `collect_up_to` to bound the memory on large queries.
+    ///
+    /// ```
+    /// use kodik_api::{list::ListQuery, types::ReleaseType, Client};
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let collected = ListQuery::new()
+    ///     .with_types(&[ReleaseType::AnimeSerial])
+    ///     .collect_all(&client)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("showing {} of {}", collected.results.len(), collected.total);
+    /// # }
+    /// ```
+    pub async fn collect_all(&self, client: &Client) -> Result<CollectedReleases, Error> {
+        self.collect(client, None).await
+    }
+
+    /// Same as `collect_all`, but stops after `limit` releases without fetching further pages
+    pub async fn collect_up_to(
+        &self,
+        client: &Client,
+        limit: usize,
+    ) -> Result<CollectedReleases, Error> {
+        self.collect(client, Some(limit)).await
+    }
+
+    async fn collect(
+        &self,
+        client: &Client,
+        limit: Option<usize>,
+    ) -> Result<CollectedReleases, Error> {
+        let stream = self.stream(client);
+
+        pin_mut!(stream);
+
+        let mut collected = CollectedReleases::default();
+        let mut seen_ids = HashSet::new();
+
+        let is_full = |collected: &CollectedReleases| {
+            limit.is_some_and(|limit| collected.results.len() >= limit)
+        };
+
+        while !is_full(&collected) {
+            let Some(response) = stream.next().await else {
+                break;
+            };
+
+            let response = response?;
+
+            if collected.pages == 0 {
+                collected.total = response.total;
+            }
+
+            collected.pages += 1;
+
+            for release in response.results {
+                if is_full(&collected) {
+                    break;
+                }
+
+                if self.deduplicate && !seen_ids.insert(release.id.clone()) {
+                    continue;
+                }
+
+                collected.results.push(release);
+            }
+        }
+
+        Ok(collected)
+    }
+
+    /// Counts the query releases across all pages by type, year, quality and translation type
+    ///
+    /// Only the counters are kept in memory. Respects `with_deduplication`. Fails on the first error instead of retrying the page.
+    pub async fn stream_stats(&self, client: &Client) -> Result<CatalogStats, Error> {
+        let stream = self.stream_releases(client);
+
+        pin_mut!(stream);
+
+        let mut stats = CatalogStats::default();
+
+        while let Some(release) = stream.next().await {
+            stats.add(&release?);
+        }
+
+        Ok(stats)
+    }
 }
 
 impl<'a> Default for ListQuery<'a> {
@@ -652,3 +1722,1103 @@ impl<'a> Default for ListQuery<'a> {
         Self::new()
     }
 }
+
+impl Client {
+    /// Create a [`ListQuery`] bound to this client, so it can be executed with [`ListQuery::send`]
+    ///
+    /// ```
+    /// use kodik_api::Client;
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let response = client
+    ///     .list()
+    ///     .with_limit(10)
+    ///     .send()
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("{response:#?}");
+    /// # }
+    /// ```
+    pub fn list<'a>(&self) -> ListQuery<'a> {
+        ListQuery {
+            client: Some(self.clone()),
+            ..ListQuery::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::TryStreamExt;
+    use serde_json::json;
+    use wiremock::{
+        matchers::{method, path, query_param, query_param_is_missing},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        transport::MockTransport, types::tests::get_default_kodik_release, ClientBuilder,
+        RetryPolicy,
+    };
+
+    use super::*;
+
+    fn get_list_page(ids: &[&str], next_page: Option<String>) -> serde_json::Value {
+        let results = ids
+            .iter()
+            .map(|id| {
+                let mut release = get_default_kodik_release();
+
+                release.id = (*id).to_owned();
+
+                release
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "time": "3ms",
+            "total": 3,
+            "prev_page": null,
+            "next_page": next_page,
+            "results": results,
+        })
+    }
+
+    async fn get_mock_server_with_pages(pages: &[&[&str]]) -> MockServer {
+        get_mock_server_with_pages_on(MockServer::start().await, pages).await
+    }
+
+    async fn get_mock_server_with_pages_on(
+        mock_server: MockServer,
+        pages: &[&[&str]],
+    ) -> MockServer {
+        for (index, ids) in pages.iter().enumerate() {
+            let next_page = (index + 1 < pages.len())
+                .then(|| format!("{}/list?page={}", mock_server.uri(), index + 2));
+
+            let mock = Mock::given(method("POST")).and(path("/list"));
+
+            let mock = if index == 0 {
+                mock.and(query_param_is_missing("page"))
+            } else {
+                mock.and(query_param("page", (index + 1).to_string()))
+            };
+
+            mock.respond_with(
+                ResponseTemplate::new(200).set_body_json(get_list_page(ids, next_page)),
+            )
+            .mount(&mock_server)
+            .await;
+        }
+
+        mock_server
+    }
+
+    fn get_client(mock_server: &MockServer) -> Client {
+        ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_stream_releases_deduplication() {
+        let mock_server =
+            get_mock_server_with_pages(&[&["serial-1", "serial-2"], &["serial-2", "serial-3"]])
+                .await;
+        let client = get_client(&mock_server);
+
+        let releases: Vec<Release> = ListQuery::new()
+            .stream_releases(&client)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            releases
+                .iter()
+                .map(|release| release.id.as_str())
+                .collect::<Vec<_>>(),
+            ["serial-1", "serial-2", "serial-2", "serial-3"]
+        );
+
+        let releases: Vec<Release> = ListQuery::new()
+            .with_deduplication(true)
+            .stream_releases(&client)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            releases
+                .iter()
+                .map(|release| release.id.as_str())
+                .collect::<Vec<_>>(),
+            ["serial-1", "serial-2", "serial-3"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_rate_limit() {
+        let mock_server = get_mock_server_with_pages(&[
+            &["serial-1"],
+            &["serial-2"],
+            &["serial-3"],
+            &["serial-4"],
+            &["serial-5"],
+        ])
+        .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .rate_limit(2, Duration::from_secs(1))
+            .build();
+
+        let started_at = Instant::now();
+
+        let pages: Vec<ListResponse> = ListQuery::new()
+            .stream(&client)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages.len(), 5);
+        // The first two pages are a burst, the other three wait for half a second each
+        assert!(started_at.elapsed() >= Duration::from_millis(1450));
+    }
+
+    #[tokio::test]
+    async fn test_stream_retry() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let mock_server = get_mock_server_with_pages_on(
+            mock_server,
+            &[&["serial-1"], &["serial-2"], &["serial-3"]],
+        )
+        .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .retry(RetryPolicy::new(3, Duration::from_millis(1)))
+            .build();
+
+        let pages: Vec<ListResponse> = ListQuery::new()
+            .stream(&client)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            pages
+                .iter()
+                .flat_map(|page| &page.results)
+                .map(|release| release.id.as_str())
+                .collect::<Vec<_>>(),
+            ["serial-1", "serial-2", "serial-3"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_resends_params() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .and(query_param_is_missing("page"))
+            .and(query_param("with_material_data", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_list_page(
+                &["serial-1"],
+                Some(format!("{}/list?page=2&limit=1", mock_server.uri())),
+            )))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .and(query_param("page", "2"))
+            .and(query_param("limit", "1"))
+            .and(query_param("with_material_data", "true"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(get_list_page(&["serial-2"], None)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = get_client(&mock_server);
+
+        let pages: Vec<ListResponse> = ListQuery::new()
+            .with_limit(1)
+            .with_material_data(true)
+            .stream(&client)
+            .take(2)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages.len(), 2);
+
+        let requests = mock_server.received_requests().await.unwrap();
+
+        assert_eq!(
+            requests[1]
+                .url
+                .query_pairs()
+                .filter(|(key, _)| key == "limit")
+                .count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collect_all() {
+        let mock_server = get_mock_server_with_pages(&[
+            &["serial-1", "serial-2"],
+            &["serial-3", "serial-4"],
+            &["serial-5"],
+        ])
+        .await;
+        let client = get_client(&mock_server);
+
+        let collected = ListQuery::new().collect_all(&client).await.unwrap();
+
+        assert_eq!(collected.results.len(), 5);
+        assert_eq!(collected.total, 3);
+        assert_eq!(collected.pages, 3);
+
+        let collected = ListQuery::new().collect_up_to(&client, 3).await.unwrap();
+
+        assert_eq!(collected.total, 3);
+        assert_eq!(collected.pages, 2);
+        assert_eq!(
+            collected
+                .results
+                .iter()
+                .map(|release| release.id.as_str())
+                .collect::<Vec<_>>(),
+            ["serial-1", "serial-2", "serial-3"]
+        );
+
+        // 3 pages for `collect_all` and 2 for `collect_up_to`
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_stream_from() {
+        let mock_server =
+            get_mock_server_with_pages(&[&["serial-1"], &["serial-2"], &["serial-3"]]).await;
+        let client = get_client(&mock_server);
+
+        let query = ListQuery::new();
+
+        let first_page = query.execute(&client).await.unwrap();
+
+        let pages: Vec<ListResponse> = query
+            .stream_from(&client, first_page.next_page)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            pages
+                .iter()
+                .flat_map(|page| page.results.iter().map(|release| release.id.as_str()))
+                .collect::<Vec<_>>(),
+            ["serial-2", "serial-3"]
+        );
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_releases_lazy() {
+        let mock_server =
+            get_mock_server_with_pages(&[&["serial-1", "serial-2"], &["serial-2", "serial-3"]])
+                .await;
+        let client = get_client(&mock_server);
+
+        let releases: Vec<Release> = ListQuery::new()
+            .with_deduplication(true)
+            .stream_releases_lazy(&client)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            releases
+                .iter()
+                .map(|release| release.id.as_str())
+                .collect::<Vec<_>>(),
+            ["serial-1", "serial-2", "serial-3"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_releases_lazy_per_release() {
+        let mock_server = MockServer::start().await;
+
+        let mut page = get_list_page(&["serial-1", "serial-2", "serial-3"], None);
+
+        // A broken release in the middle of the page fails only itself
+        page["results"][1] = json!({ "id": "serial-2" });
+
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page))
+            .mount(&mock_server)
+            .await;
+
+        let client = get_client(&mock_server);
+
+        let releases = ListQuery::new()
+            .stream_releases_lazy(&client)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert!(matches!(releases.as_slice(), [
+            Ok(first),
+            Err(Error::DecodeError { body, .. }),
+            Ok(third),
+        ] if first.id == "serial-1" && body.contains("serial-2") && third.id == "serial-3"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_releases_lazy_without_results() {
+        let mock_server = MockServer::start().await;
+
+        // Neither `results` nor `error`, which must not pass for an empty last page
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "time": "3ms" })))
+            .mount(&mock_server)
+            .await;
+
+        let client = get_client(&mock_server);
+
+        let releases = ListQuery::new()
+            .stream_releases_lazy(&client)
+            .take(2)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert!(matches!(
+            releases.first(),
+            Some(Err(Error::DecodeError { body, .. })) if body.contains("3ms")
+        ));
+
+        let result: Result<RawListResponseUnion, _> =
+            serde_json::from_str(r#"{"error":"Отсутствует или неверный токен"}"#);
+
+        assert!(matches!(result, Ok(RawListResponseUnion::Error { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_stream_limited() {
+        let mock_server = get_mock_server_with_pages(&[
+            &["serial-1"],
+            &["serial-2"],
+            &["serial-3"],
+            &["serial-4"],
+            &["serial-5"],
+        ])
+        .await;
+        let client = get_client(&mock_server);
+
+        let pages: Vec<ListResponse> = ListQuery::new()
+            .stream_limited(&client, 2)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            pages
+                .iter()
+                .flat_map(|page| page.results.iter().map(|release| release.id.as_str()))
+                .collect::<Vec<_>>(),
+            ["serial-1", "serial-2"]
+        );
+        // Stopped due to the budget, the next page is still known
+        assert_eq!(
+            pages.last().unwrap().next_page,
+            Some(format!("{}/list?page=3", mock_server.uri()))
+        );
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+        let pages: Vec<ListResponse> = ListQuery::new()
+            .stream_limited(&client, 10)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages.len(), 5);
+        assert_eq!(pages.last().unwrap().next_page, None);
+    }
+
+    #[test]
+    fn test_async_std() {
+        let transport = MockTransport::new();
+
+        transport.respond(
+            "/list",
+            200,
+            get_list_page(&["serial-1", "serial-2"], None).to_string(),
+        );
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport)
+            .build();
+
+        // No Tokio runtime is running here
+        let releases: Vec<Release> =
+            async_std::task::block_on(ListQuery::new().stream_releases(&client).try_collect())
+                .unwrap();
+
+        assert_eq!(
+            releases
+                .iter()
+                .map(|release| release.id.as_str())
+                .collect::<Vec<_>>(),
+            ["serial-1", "serial-2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_until() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .and(query_param_is_missing("page"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_list_page(
+                &["serial-1"],
+                Some(format!("{}/list?page=2", mock_server.uri())),
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // The second page is still in flight when the stream is cancelled
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(get_list_page(
+                        &["serial-2"],
+                        Some(format!("{}/list?page=3", mock_server.uri())),
+                    ))
+                    .set_delay(Duration::from_secs(10)),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .and(query_param("page", "3"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(get_list_page(&["serial-3"], None)),
+            )
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let client = get_client(&mock_server);
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+
+        let stream = ListQuery::new().stream_until(&client, async {
+            let _ = shutdown_rx.await;
+        });
+
+        pin_mut!(stream);
+
+        let page = stream.next().await.unwrap().unwrap();
+
+        assert_eq!(page.results[0].id, "serial-1");
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+
+            shutdown_tx.send(()).unwrap();
+        });
+
+        let started_at = Instant::now();
+
+        assert!(stream.next().await.is_none());
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stream_limited_errors() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let client = get_client(&mock_server);
+
+        let results: Vec<Result<ListResponse, Error>> =
+            ListQuery::new().stream_limited(&client, 3).collect().await;
+
+        assert_eq!(results.len(), 3);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Err(Error::HttpStatus { status: 500, .. }))));
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_pagination_host() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/list"))
+            .and(query_param_is_missing("page"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_list_page(
+                &["serial-1"],
+                Some("https://kodikapi.com/list?page=2".to_owned()),
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/list"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(get_list_page(&["serial-2"], None)),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(format!("{}/api", mock_server.uri()))
+            .rewrite_pagination_host(true)
+            .build();
+
+        let pages: Vec<ListResponse> = ListQuery::new()
+            .stream(&client)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages.len(), 2);
+    }
+
+    #[test]
+    fn test_stream_readahead() {
+        let transport = MockTransport::new();
+
+        // Every page has its own path, since the mock transport responds by the path
+        for index in 1..=10 {
+            let next_page =
+                (index < 10).then(|| format!("https://kodikapi.com/page-{}", index + 1));
+
+            let path = if index == 1 {
+                "/list".to_owned()
+            } else {
+                format!("/page-{index}")
+            };
+
+            transport.respond(
+                &path,
+                200,
+                get_list_page(&[&format!("serial-{index}")], next_page).to_string(),
+            );
+        }
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport.clone())
+            .build();
+
+        // Outside of any runtime and not polled yet
+        let stream = ListQuery::new().stream_readahead(&client);
+
+        assert!(transport.requests().is_empty());
+
+        async_std::task::block_on(async {
+            pin_mut!(stream);
+
+            let mut ids = vec![];
+
+            while let Some(response) = stream.next().await {
+                ids.extend(
+                    response
+                        .unwrap()
+                        .results
+                        .into_iter()
+                        .map(|release| release.id),
+                );
+
+                // At most the request for the next page is ahead of the consumer
+                assert_eq!(transport.requests().len(), (ids.len() + 1).min(10));
+            }
+
+            assert_eq!(
+                ids,
+                (1..=10)
+                    .map(|index| format!("serial-{index}"))
+                    .collect::<Vec<_>>()
+            );
+        });
+
+        assert_eq!(transport.requests().len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_stream_into_channel() {
+        let mock_server =
+            get_mock_server_with_pages(&[&["serial-1", "serial-2"], &["serial-3"]]).await;
+        let client = get_client(&mock_server);
+
+        let (tx, mut rx) = mpsc::channel::<Result<Release, Error>>(1);
+
+        let consumer = tokio::spawn(async move {
+            let mut ids = vec![];
+
+            while let Some(release) = rx.recv().await {
+                ids.push(release.unwrap().id);
+            }
+
+            ids
+        });
+
+        ListQuery::new().stream_into_channel(&client, tx).await;
+
+        assert_eq!(
+            consumer.await.unwrap(),
+            ["serial-1", "serial-2", "serial-3"]
+        );
+
+        let mock_server =
+            get_mock_server_with_pages(&[&["serial-1", "serial-2", "serial-3"], &["serial-4"]])
+                .await;
+        let client = get_client(&mock_server);
+
+        let (tx, mut rx) = mpsc::channel::<Result<Release, Error>>(1);
+
+        let consumer = tokio::spawn(async move { rx.recv().await.unwrap().unwrap().id });
+
+        ListQuery::new().stream_into_channel(&client, tx).await;
+
+        assert_eq!(consumer.await.unwrap(), "serial-1");
+        // The second page is not fetched after the receiver is dropped
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_stats() {
+        let mock_server =
+            get_mock_server_with_pages(&[&["serial-1", "serial-2"], &["serial-2", "serial-3"]])
+                .await;
+        let client = get_client(&mock_server);
+
+        let stats = ListQuery::new()
+            .with_deduplication(true)
+            .stream_stats(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            stats,
+            CatalogStats {
+                total: 3,
+                by_release_type: HashMap::from([(ReleaseType::AnimeSerial, 3)]),
+                by_year: BTreeMap::from([(2022, 3)]),
+                by_quality: HashMap::from([(ReleaseQuality::WebDlRip720p, 3)]),
+                by_translation_type: HashMap::from([(TranslationType::Voice, 3)]),
+            }
+        );
+
+        let stats = ListQuery::new().stream_stats(&client).await.unwrap();
+
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.by_year, BTreeMap::from([(2022, 4)]));
+    }
+
+    #[test]
+    fn test_exclude_ids() {
+        let mut response: ListResponse =
+            serde_json::from_value(get_list_page(&["serial-1", "movie-1", "serial-2"], None))
+                .unwrap();
+
+        response.exclude_ids(&["serial-1", "serial-"]);
+
+        assert_eq!(
+            response
+                .results
+                .iter()
+                .map(|release| release.id.as_str())
+                .collect::<Vec<_>>(),
+            ["movie-1", "serial-2"]
+        );
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut query = ListQuery::new();
+
+        query
+            .with_duration_range(DurationRange::Between(20, 30))
+            .with_kinopoisk_rating_range(Range::AtLeast(7.5))
+            .with_imdb_rating_range(Range::Exact(8.0))
+            .with_shikimori_rating_range(Range::Between(6.5, 8.0))
+            .with_mydramalist_rating_range(Range::AtMost(5.0));
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [
+                ("duration".to_owned(), "20-30".to_owned()),
+                ("kinopoisk_rating".to_owned(), "7.5-10".to_owned()),
+                ("imdb_rating".to_owned(), "8".to_owned()),
+                ("shikimori_rating".to_owned(), "6.5-8".to_owned()),
+                ("mydramalist_rating".to_owned(), "0-5".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_auto_material_data() {
+        let has_material_data = |query: &ListQuery| {
+            query
+                .query_parts()
+                .unwrap()
+                .into_iter()
+                .find(|(key, _)| key == "with_material_data")
+                .map(|(_, value)| value)
+        };
+
+        assert_eq!(has_material_data(&ListQuery::new()), None);
+        assert_eq!(
+            has_material_data(ListQuery::new().with_genres(&["аниме"])),
+            Some("true".to_owned())
+        );
+        assert_eq!(
+            has_material_data(ListQuery::new().with_kinopoisk_rating_range(Range::AtLeast(7.0))),
+            Some("true".to_owned())
+        );
+        assert_eq!(
+            has_material_data(
+                ListQuery::new()
+                    .with_actors(&["Дзюнъити Сувабэ"])
+                    .with_material_data(false)
+            ),
+            Some("false".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_sort_from_str() {
+        for sort in [
+            ListSort::Year,
+            ListSort::CreatedAt,
+            ListSort::UpdatedAt,
+            ListSort::KinopoiskRating,
+            ListSort::ImdbRating,
+            ListSort::ShikimoriRating,
+        ] {
+            assert_eq!(sort.to_string().parse::<ListSort>().unwrap(), sort);
+            assert_eq!(serde_json::to_value(sort).unwrap(), sort.as_str());
+        }
+
+        for order in [ListOrder::Asc, ListOrder::Desc] {
+            assert_eq!(order.to_string().parse::<ListOrder>().unwrap(), order);
+            assert_eq!(serde_json::to_value(order).unwrap(), order.as_str());
+        }
+
+        assert_eq!(ListSort::default(), ListSort::UpdatedAt);
+        assert_eq!(ListOrder::default(), ListOrder::Desc);
+
+        assert!(matches!(
+            "updated".parse::<ListSort>(),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            "DESC".parse::<ListOrder>(),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_zero_limit() {
+        let client = Client::new("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7");
+
+        let mut query = ListQuery::new();
+
+        query.with_limit(0);
+
+        assert!(matches!(query.validate(), Err(Error::InvalidQuery(_))));
+        assert!(matches!(
+            query.execute(&client).await,
+            Err(Error::InvalidQuery(_))
+        ));
+
+        let pages = query.stream(&client).collect::<Vec<_>>().await;
+
+        assert!(matches!(pages.as_slice(), [Err(Error::InvalidQuery(_))]));
+    }
+
+    #[test]
+    fn test_not_blocked_in() {
+        let mut query = ListQuery::new();
+
+        query.with_not_blocked_in(vec![
+            CountryCode::new("RU").unwrap(),
+            CountryCode::new("UA").unwrap(),
+        ]);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [("not_blocked_in".to_owned(), "RU,UA".to_owned())]
+        );
+
+        query.with_not_blocked_for_me(true);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap()[1],
+            ("not_blocked_for_me".to_owned(), "true".to_owned())
+        );
+
+        query.with_not_blocked_for_me(false);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap()[1],
+            ("not_blocked_for_me".to_owned(), "false".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_with_category() {
+        let mut query = ListQuery::new();
+
+        query
+            .with_types(&[ReleaseType::ForeignMovie])
+            .with_category(&[Category::Anime, Category::Documentaries]);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [(
+                "types".to_owned(),
+                "anime,anime-serial,documentary-serial".to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_owned_query() {
+        fn get_query(genres: &[String]) -> ListQuery<'static> {
+            ListQuery::new()
+                .with_types(vec![ReleaseType::AnimeSerial])
+                .with_year(vec![Year::new(2022).unwrap()])
+                .with_genres(genres.to_vec())
+                .with_translation_id(vec![610, 609])
+                .clone()
+        }
+
+        let query = get_query(&["аниме".to_owned(), "драма".to_owned()]);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [
+                ("types".to_owned(), "anime-serial".to_owned()),
+                ("year".to_owned(), "2022".to_owned()),
+                ("translation_id".to_owned(), "610,609".to_owned()),
+                ("genres".to_owned(), "аниме,драма".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_types_skips_unknown() {
+        let mut query = ListQuery::new();
+
+        query.with_types(&[ReleaseType::Unknown, ReleaseType::AnimeSerial]);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [("types".to_owned(), "anime-serial".to_owned())]
+        );
+
+        query.with_types(&[ReleaseType::Unknown]);
+
+        assert!(matches!(query.validate(), Err(Error::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut progress = ListProgress::new();
+
+        assert_eq!(progress.fraction(), None);
+
+        progress.update(&ListResponse {
+            total: 4,
+            results: vec![get_default_kodik_release()],
+            ..Default::default()
+        });
+
+        assert_eq!(progress, ListProgress::resume(1, Some(4)));
+        assert_eq!(progress.fraction(), Some(0.25));
+
+        let mut progress = ListProgress::resume(2, Some(4));
+
+        assert_eq!(progress.fraction(), Some(0.5));
+
+        // The total is refreshed from the next page
+        progress.update(&ListResponse {
+            total: 3,
+            results: vec![get_default_kodik_release()],
+            ..Default::default()
+        });
+
+        assert_eq!(progress.fraction(), Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_stream_from_with_progress() {
+        let mock_server =
+            get_mock_server_with_pages(&[&["serial-1"], &["serial-2", "serial-3"]]).await;
+
+        let client = get_client(&mock_server);
+
+        let progress = ListQuery::new()
+            .stream_from_with_progress(
+                &client,
+                Some(format!("{}/list?page=2", mock_server.uri())),
+                ListProgress::resume(1, Some(3)),
+            )
+            .map_ok(|(_, progress)| progress)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        assert_eq!(progress, [ListProgress::resume(3, Some(3))]);
+        assert_eq!(progress[0].fraction(), Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_comma_in_list_value() {
+        let mock_server = MockServer::start().await;
+        let client = get_client(&mock_server);
+
+        let result = ListQuery::new()
+            .with_actors(&["Smith, John", "Jane Doe"])
+            .execute(&client)
+            .await;
+
+        assert!(
+            matches!(result, Err(Error::InvalidParameter(message)) if message.contains("actors"))
+        );
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+
+        assert!(ListQuery::new()
+            .with_actors(&["John Smith", "Jane Doe"])
+            .validate()
+            .is_ok());
+        assert_eq!(
+            serialize_into_query_parts(ListQuery::new().with_actors(&["John Smith", "Jane Doe"]))
+                .unwrap(),
+            [("actors".to_owned(), "John Smith,Jane Doe".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_results_and_kodik_error() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .and(query_param("types", "anime"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "time": "3ms",
+                "total": 0,
+                "prev_page": null,
+                "next_page": null,
+                "results": [],
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/list"))
+            .and(query_param_is_missing("types"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "error": "Отсутствует или неверный токен" })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = get_client(&mock_server);
+
+        let response = ListQuery::new()
+            .with_types(&[ReleaseType::Anime])
+            .execute(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(response.total, 0);
+        assert!(response.results.is_empty());
+
+        let pages: Vec<ListResponse> = ListQuery::new()
+            .with_types(&[ReleaseType::Anime])
+            .stream(&client)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].results.is_empty());
+
+        let result = ListQuery::new().execute(&client).await;
+
+        assert!(
+            matches!(result, Err(Error::KodikError(message)) if message == "Отсутствует или неверный токен")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_response() {
+        let transport = MockTransport::new();
+
+        transport.respond(
+            "/list",
+            200,
+            r#"{"time":"3ms","total":0,"prev_page":null,"next_page":null,"results":[]}"#,
+        );
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport.clone())
+            .build();
+
+        let response = ListQuery::new().execute(&client).await.unwrap();
+
+        assert_eq!(response.total, 0);
+        assert!(response.results.is_empty());
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+/// Records a finished request
+///
+/// `outcome` is one of `"success"`, `"kodik_error"`, `"http_error"` or `"decode_error"`.
+#[cfg(feature = "metrics")]
+pub(crate) fn record_request(endpoint: &str, outcome: &'static str, elapsed: Duration) {
+    let labels = [
+        ("endpoint", endpoint.to_owned()),
+        ("outcome", outcome.to_owned()),
+    ];
+
+    ::metrics::counter!("kodik_api_requests_total", &labels).increment(1);
+    ::metrics::histogram!("kodik_api_request_duration_seconds", &labels)
+        .record(elapsed.as_secs_f64());
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn record_request(_endpoint: &str, _outcome: &'static str, _elapsed: Duration) {}
+
+/// Records a page received by a stream or a pager
+///
+/// `outcome` is one of `"success"`, `"kodik_error"` or `"error"`.
+#[cfg(feature = "metrics")]
+pub(crate) fn record_stream_page(endpoint: &str, outcome: &'static str) {
+    let labels = [
+        ("endpoint", endpoint.to_owned()),
+        ("outcome", outcome.to_owned()),
+    ];
+
+    ::metrics::counter!("kodik_api_stream_pages_total", &labels).increment(1);
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn record_stream_page(_endpoint: &str, _outcome: &'static str) {}
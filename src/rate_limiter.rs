@@ -0,0 +1,62 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures_timer::Delay;
+
+/// Token bucket limiter, shared between clones of the client
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    capacity: f64,
+    tokens_per_second: f64,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    /// Allows bursts of up to `max_requests` and `max_requests` requests per `per` on average
+    pub(crate) fn new(max_requests: u32, per: Duration) -> RateLimiter {
+        let capacity = f64::from(max_requests.max(1));
+
+        RateLimiter {
+            capacity,
+            tokens_per_second: capacity / per.as_secs_f64().max(f64::EPSILON),
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: capacity,
+                updated_at: Instant::now(),
+            })),
+        }
+    }
+
+    /// Waits until a request can be sent
+    pub(crate) async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().expect("rate limiter lock poisoned");
+
+                let now = Instant::now();
+                let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+
+                bucket.tokens =
+                    (bucket.tokens + elapsed * self.tokens_per_second).min(self.capacity);
+                bucket.updated_at = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+
+                    return;
+                }
+
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.tokens_per_second)
+            };
+
+            Delay::new(wait).await;
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures_util::future::try_join5;
+
+use crate::{
+    countries::{CountryQuery, CountryResult},
+    error::Error,
+    genres::{GenreQuery, GenreResult},
+    qualities::{QualityQuery, QualityResult},
+    translations::{TranslationQuery, TranslationResult},
+    years::{YearQuery, YearResult},
+    Client,
+};
+
+/// A snapshot of the slowly changing reference data: translations, genres, countries, years and qualities
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceData {
+    /// Translations by their ID
+    pub translations: HashMap<i32, TranslationResult>,
+
+    pub genres: Vec<GenreResult>,
+
+    pub countries: Vec<CountryResult>,
+
+    pub years: Vec<YearResult>,
+
+    pub qualities: Vec<QualityResult>,
+}
+
+impl ReferenceData {
+    /// Returns the translation with the given ID
+    pub fn translation(&self, id: i32) -> Option<&TranslationResult> {
+        self.translations.get(&id)
+    }
+
+    /// Returns the name of the translation team with the given ID
+    pub fn translation_title(&self, id: i32) -> Option<&str> {
+        self.translation(id)
+            .map(|translation| translation.title.as_str())
+    }
+
+    /// Returns the genre with the given title, case insensitive like the API
+    pub fn genre(&self, title: &str) -> Option<&GenreResult> {
+        self.genres
+            .iter()
+            .find(|genre| genre.title.to_lowercase() == title.to_lowercase())
+    }
+
+    /// Returns the country with the given title, case sensitive like the API
+    pub fn country(&self, title: &str) -> Option<&CountryResult> {
+        self.countries.iter().find(|country| country.title == title)
+    }
+
+    /// Returns the year with the given number
+    pub fn year(&self, year: i32) -> Option<&YearResult> {
+        self.years.iter().find(|result| result.year == year)
+    }
+
+    /// Returns the quality with the given title
+    pub fn quality(&self, title: &str) -> Option<&QualityResult> {
+        self.qualities.iter().find(|quality| quality.title == title)
+    }
+}
+
+type Snapshot = Option<(Instant, Arc<ReferenceData>)>;
+
+/// Cached reference data, shared between clones of the client
+#[derive(Debug, Clone)]
+pub(crate) struct ReferenceDataCache {
+    ttl: Duration,
+    snapshot: Arc<Mutex<Snapshot>>,
+}
+
+impl ReferenceDataCache {
+    pub(crate) fn new(ttl: Duration) -> ReferenceDataCache {
+        ReferenceDataCache {
+            ttl,
+            snapshot: Arc::default(),
+        }
+    }
+
+    fn get(&self) -> Option<Arc<ReferenceData>> {
+        let snapshot = self.snapshot.lock().expect("reference data lock poisoned");
+
+        snapshot
+            .as_ref()
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, reference_data)| reference_data.clone())
+    }
+
+    fn set(&self, reference_data: Arc<ReferenceData>) {
+        *self.snapshot.lock().expect("reference data lock poisoned") =
+            Some((Instant::now(), reference_data));
+    }
+}
+
+impl Client {
+    /// Fetches translations, genres, countries, years and qualities concurrently into a single snapshot
+    ///
+    /// If [`crate::ClientBuilder::reference_data_ttl`] is set, the snapshot is cached for that long and shared between clones of the client. The first call after the TTL expires fetches a new snapshot, a failed fetch is not cached. Without a TTL every call fetches the data again.
+    ///
+    /// ```
+    /// use kodik_api::Client;
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let reference_data = client.reference_data().await.unwrap();
+    ///
+    /// println!("{:?}", reference_data.translation_title(610));
+    /// # }
+    /// ```
+    pub async fn reference_data(&self) -> Result<Arc<ReferenceData>, Error> {
+        if let Some(reference_data) = self
+            .reference_data_cache
+            .as_ref()
+            .and_then(ReferenceDataCache::get)
+        {
+            return Ok(reference_data);
+        }
+
+        let (translations, genres, countries, years, qualities) = try_join5(
+            TranslationQuery::new().execute(self),
+            GenreQuery::new().execute(self),
+            CountryQuery::new().execute(self),
+            YearQuery::new().execute(self),
+            QualityQuery::new().execute(self),
+        )
+        .await?;
+
+        let reference_data = Arc::new(ReferenceData {
+            translations: translations
+                .results
+                .into_iter()
+                .map(|translation| (translation.id, translation))
+                .collect(),
+            genres: genres.results,
+            countries: countries.results,
+            years: years.results,
+            qualities: qualities.results,
+        });
+
+        if let Some(cache) = &self.reference_data_cache {
+            cache.set(reference_data.clone());
+        }
+
+        Ok(reference_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use crate::ClientBuilder;
+
+    use super::*;
+
+    async fn get_mock_server() -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        let responses = [
+            (
+                "/translations/v2",
+                json!([{ "id": 610, "title": "AniLibria.TV", "count": 1234 }]),
+            ),
+            ("/genres", json!([{ "title": "аниме", "count": 4321 }])),
+            ("/countries", json!([{ "title": "Япония", "count": 2345 }])),
+            ("/years", json!([{ "year": 2022, "count": 3456 }])),
+            (
+                "/qualities/v2",
+                json!([{ "title": "WEB-DLRip 720p", "count": 5678 }]),
+            ),
+        ];
+
+        for (endpoint, results) in responses {
+            Mock::given(method("POST"))
+                .and(path(endpoint))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "time": "3ms",
+                    "total": 1,
+                    "prev_page": null,
+                    "next_page": null,
+                    "results": results,
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_reference_data() {
+        let mock_server = get_mock_server().await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .reference_data_ttl(Duration::from_secs(60))
+            .build();
+
+        let reference_data = client.reference_data().await.unwrap();
+
+        assert_eq!(reference_data.translation_title(610), Some("AniLibria.TV"));
+        assert_eq!(reference_data.translation_title(611), None);
+        assert_eq!(reference_data.genre("Аниме").unwrap().count, 4321);
+        assert_eq!(reference_data.country("Япония").unwrap().count, 2345);
+        assert_eq!(reference_data.year(2022).unwrap().count, 3456);
+        assert_eq!(
+            reference_data.quality("WEB-DLRip 720p").unwrap().count,
+            5678
+        );
+
+        // Served from the cache shared with the clone, the mocks expect a single request each
+        let cached_reference_data = client.clone().reference_data().await.unwrap();
+
+        assert!(Arc::ptr_eq(&reference_data, &cached_reference_data));
+    }
+
+    #[tokio::test]
+    async fn test_reference_data_shared_between_clones() {
+        let mock_server = get_mock_server().await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .reference_data_ttl(Duration::from_secs(60))
+            .build();
+
+        // Both clones are made before the cache is populated
+        let populating_client = client.clone();
+        let reading_client = client.clone();
+
+        drop(client);
+
+        let reference_data = populating_client.reference_data().await.unwrap();
+
+        // Each worker task reads through its own clone, the mocks expect a single request each
+        let handles = (0..4)
+            .map(|_| {
+                let reading_client = reading_client.clone();
+
+                tokio::spawn(async move { reading_client.reference_data().await.unwrap() })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            assert!(Arc::ptr_eq(&reference_data, &handle.await.unwrap()));
+        }
+    }
+}
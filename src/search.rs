@@ -1,25 +1,356 @@
+use std::{borrow::Cow, fmt};
+
+use async_fn_stream::{fn_stream, try_fn_stream};
+use futures_util::{pin_mut, stream, Stream, StreamExt};
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    client::redact_token,
     error::Error,
+    list::{fetch_page, stream_pages_from, PageResponseUnion},
     types::{
-        AllStatus, AnimeKind, AnimeStatus, DramaStatus, MaterialDataField, MppaRating, Release,
-        ReleaseType, TranslationType,
+        AllStatus, AnimeKind, AnimeStatus, Category, CountryCode, DramaStatus, DurationRange,
+        IntoQueryList, MaterialDataField, MppaRating, NumericKey, ProcessingTime, Range, Release,
+        ReleaseType, TranslationPriority, TranslationType, Year,
+    },
+    unify_seasons::{unify_seasons, UnifiedSeason},
+    util::{
+        deserialize_number_or_string, into_owned_list, into_owned_str, into_owned_str_list,
+        range_query_list, reject_commas_in_lists, serialize_into_query_parts, ResponseUnion,
     },
-    util::serialize_into_query_parts,
-    Client,
+    Client, FailureMode, ResponseMeta,
 };
 
 /// A struct containing search results and other information about the search
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SearchResponse {
     pub time: String,
+    #[serde(deserialize_with = "deserialize_number_or_string")]
     pub total: i32,
     pub prev_page: Option<String>,
     pub next_page: Option<String>,
     pub results: Vec<Release>,
 }
 
+impl ProcessingTime for SearchResponse {
+    fn time(&self) -> &str {
+        &self.time
+    }
+}
+
+impl SearchResponse {
+    /// Checks whether the results respect the given translation priority
+    ///
+    /// `priority` is a list of translation IDs, the leftmost has the highest priority. Translations that are not in the list are expected after all listed ones.
+    pub fn verify_translation_priority(&self, priority: &[i32]) -> bool {
+        self.results.windows(2).all(|pair| {
+            translation_rank(&pair[0], priority) <= translation_rank(&pair[1], priority)
+        })
+    }
+
+    /// Reorders the results by the given translation priority
+    ///
+    /// `priority` is a list of translation IDs, the leftmost has the highest priority. Translations that are not in the list go after all listed ones. The sort is stable, so the server order is kept within the same priority.
+    pub fn reorder_by_priority(&mut self, priority: &[i32]) {
+        self.results
+            .sort_by_key(|release| translation_rank(release, priority));
+    }
+
+    /// Drops the releases with the given IDs from the results
+    ///
+    /// IDs are matched exactly, including the `movie-` or `serial-` prefix. Every translation of a title is a separate release with its own ID, so other translations of an excluded release are kept. `total` is left as returned by the API.
+    pub fn exclude_ids(&mut self, ids: &[&str]) {
+        self.results
+            .retain(|release| !ids.contains(&release.id.as_str()));
+    }
+
+    /// Returns the release whose title is the most similar to the query, see [`title_similarity`]
+    ///
+    /// `title`, `title_orig` and every title of `other_title` separated by ` / ` are compared, the best of them counts. The first release wins among equally similar ones.
+    pub fn best_title_match(&self, query: &str) -> Option<&Release> {
+        let mut best: Option<(&Release, f32)> = None;
+
+        for release in &self.results {
+            let other_titles = release
+                .other_title
+                .iter()
+                .flat_map(|other_title| other_title.split(" / "));
+
+            let similarity = [release.title.as_str(), release.title_orig.as_str()]
+                .into_iter()
+                .chain(other_titles)
+                .map(|title| title_similarity(query, title))
+                .fold(0.0, f32::max);
+
+            if best.map_or(true, |(_, best_similarity)| similarity > best_similarity) {
+                best = Some((release, similarity));
+            }
+        }
+
+        best.map(|(release, _)| release)
+    }
+
+    /// Returns the cursor of the next page, if there is one
+    pub fn next_cursor(&self) -> Option<PageCursor> {
+        self.next_page.clone().map(PageCursor::from)
+    }
+
+    /// Returns the cursor of the previous page, if there is one
+    pub fn prev_cursor(&self) -> Option<PageCursor> {
+        self.prev_page.clone().map(PageCursor::from)
+    }
+}
+
+/// Position of a page of search results, made from the `next_page` or `prev_page` link
+///
+/// The link contains the API key, so it is redacted in the `Debug` output.
+///
+/// ```
+/// use kodik_api::{search::SearchQuery, Client};
+///
+/// # async fn run() {
+/// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+///
+/// let first_page = SearchQuery::new()
+///     .with_title("Naruto")
+///     .execute(&client)
+///     .await
+///     .unwrap();
+///
+/// if let Some(cursor) = first_page.next_cursor() {
+///     let second_page = client.fetch_page(&cursor).await.unwrap();
+///
+///     println!("second page = {second_page:#?}");
+/// }
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PageCursor(String);
+
+impl PageCursor {
+    /// Returns the link of the page
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for PageCursor {
+    fn from(url: String) -> Self {
+        PageCursor(url)
+    }
+}
+
+impl From<&str> for PageCursor {
+    fn from(url: &str) -> Self {
+        PageCursor(url.to_owned())
+    }
+}
+
+impl fmt::Debug for PageCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let url = Url::parse(&self.0)
+            .map(|url| redact_token(&url))
+            .unwrap_or_else(|_| self.0.clone());
+
+        f.debug_tuple("PageCursor").field(&url).finish()
+    }
+}
+
+/// Pages of search results by their number, for example for a grid with a fixed page size
+///
+/// Kodik pages with links to the next page instead of offsets, so a page is reached by following `next_page` from the nearest page visited before. Only the links are cached and not the results, so the memory grows only by a link per visited page. Pages are numbered from `0`. Created with [`SearchQuery::pager`].
+///
+/// ```
+/// use kodik_api::{search::SearchQuery, Client};
+///
+/// # async fn run() {
+/// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+///
+/// let mut pager = SearchQuery::new()
+///     .with_title("Naruto")
+///     .pager(&client, 20)
+///     .unwrap();
+///
+/// if let Some(third_page) = pager.page(2).await.unwrap() {
+///     println!("third page = {third_page:#?}");
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchPager {
+    client: Client,
+    payload: Vec<(String, String)>,
+    accept_language: Option<String>,
+    /// Cursors of the pages after the first one, the cursor of the page `n` is at `n - 1`
+    cursors: Vec<PageCursor>,
+    last_page: Option<usize>,
+}
+
+impl SearchPager {
+    /// Fetch the page with the given number, or `None` if there are fewer pages
+    ///
+    /// Pages between the nearest visited page and the requested one are fetched one by one. Once the last page is reached, pages after it return `None` without a request.
+    pub async fn page(&mut self, number: usize) -> Result<Option<SearchResponse>, Error> {
+        if self.last_page.is_some_and(|last_page| number > last_page) {
+            return Ok(None);
+        }
+
+        let mut current = number.min(self.cursors.len());
+
+        loop {
+            let response = self.fetch(current).await?;
+
+            match response.next_cursor() {
+                Some(cursor) if current == self.cursors.len() => self.cursors.push(cursor),
+                Some(_) => {}
+                None => self.last_page = Some(current),
+            }
+
+            if current == number {
+                return Ok(Some(response));
+            }
+
+            if response.next_page.is_none() {
+                return Ok(None);
+            }
+
+            current += 1;
+        }
+    }
+
+    /// Returns the number of pages visited so far, including the first one
+    pub fn visited_pages(&self) -> usize {
+        self.cursors.len() + 1
+    }
+
+    async fn fetch(&self, number: usize) -> Result<SearchResponse, Error> {
+        let next_page = number
+            .checked_sub(1)
+            .map(|index| self.cursors[index].as_str());
+
+        fetch_page::<SearchResponseUnion>(
+            &self.client,
+            "/search",
+            &self.payload,
+            self.accept_language.as_deref(),
+            next_page,
+        )
+        .await
+        .map(|(response, _)| response)
+    }
+}
+
+/// Returns the similarity of two titles from `0.0` to `1.0`
+///
+/// Titles are compared case-insensitively and without punctuation, Cyrillic letters are transliterated to Latin. The similarity is the normalized Levenshtein distance of the results.
+///
+/// ```
+/// use kodik_api::search::title_similarity;
+///
+/// assert_eq!(title_similarity("Naruto: Shippuden", "naruto shippuden"), 1.0);
+/// assert_eq!(title_similarity("Наруто", "Naruto"), 1.0);
+/// assert!(title_similarity("Naruto", "Bleach") < 0.5);
+/// ```
+pub fn title_similarity(a: &str, b: &str) -> f32 {
+    let a = normalize_title(a);
+    let b = normalize_title(b);
+
+    let len = a.len().max(b.len());
+
+    if len == 0 {
+        return 1.0;
+    }
+
+    1.0 - levenshtein(&a, &b) as f32 / len as f32
+}
+
+fn normalize_title(title: &str) -> Vec<char> {
+    let mut normalized = vec![];
+
+    let words = title
+        .split(|char: char| !char::is_alphanumeric(char))
+        .filter(|word| !word.is_empty());
+
+    for word in words {
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+
+        for char in word.chars().flat_map(char::to_lowercase) {
+            match transliterate(char) {
+                Some(latin) => normalized.extend(latin.chars()),
+                None => normalized.push(char),
+            }
+        }
+    }
+
+    normalized
+}
+
+fn transliterate(char: char) -> Option<&'static str> {
+    let latin = match char {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'е' | 'ё' | 'э' => "e",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'й' => "i",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ы' => "y",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    };
+
+    Some(latin)
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+fn translation_rank(release: &Release, priority: &[i32]) -> usize {
+    priority
+        .iter()
+        .position(|id| *id == release.translation.id)
+        .unwrap_or(priority.len())
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(untagged)]
 enum SearchResponseUnion {
@@ -27,14 +358,35 @@ enum SearchResponseUnion {
     Error { error: String },
 }
 
+impl ResponseUnion for SearchResponseUnion {
+    fn is_error(&self) -> bool {
+        matches!(self, SearchResponseUnion::Error { .. })
+    }
+}
+
+impl PageResponseUnion for SearchResponseUnion {
+    type Page = SearchResponse;
+
+    fn into_page(self) -> Result<(SearchResponse, Option<String>), Error> {
+        match self {
+            SearchResponseUnion::Result(result) => {
+                let next_page = result.next_page.clone();
+
+                Ok((result, next_page))
+            }
+            SearchResponseUnion::Error { error } => Err(Error::KodikError(error)),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct SearchQuery<'a> {
     /// The name of the movie. It is not necessary to specify it explicitly, you can use a variant written by the user or a variant containing extra words. If you specify one of these parameters, the search will be performed on several fields at once: `title`, `title_orig`, `other_title`
     #[serde(skip_serializing_if = "Option::is_none")]
-    title: Option<&'a str>,
+    title: Option<Cow<'a, str>>,
     /// Original title. When this option is used, only the title_orig will be searched. It is not necessary to specify it explicitly, you can use a variant written by the user or a variant containing extra words
     #[serde(skip_serializing_if = "Option::is_none")]
-    title_orig: Option<&'a str>,
+    title_orig: Option<Cow<'a, str>>,
     /// If title or title_orig parameter was specified, this parameter defines the severity of checking if the title of the material corresponds to the search query. If true, the search results will show only those materials in which the word order is exactly the same as in the search query (but extra words in the search query are still allowed)
     #[serde(skip_serializing_if = "Option::is_none")]
     strict: Option<bool>,
@@ -44,33 +396,33 @@ pub struct SearchQuery<'a> {
 
     /// Search by Kodik ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    id: Option<&'a str>,
+    id: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Search for any link to the player
     #[serde(skip_serializing_if = "Option::is_none")]
-    player_link: Option<&'a str>,
+    player_link: Option<Cow<'a, str>>,
 
     /// Search by kinopoisk ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    kinopoisk_id: Option<&'a str>,
+    kinopoisk_id: Option<Cow<'a, str>>,
     /// Search by IMDb ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    imdb_id: Option<&'a str>,
+    imdb_id: Option<Cow<'a, str>>,
     /// Search by MyDramaList ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    mdl_id: Option<&'a str>,
+    mdl_id: Option<Cow<'a, str>>,
 
     /// Search for World Art IDs in the anime section (World Art has different content sections, each with their own independent IDs)
     #[serde(skip_serializing_if = "Option::is_none")]
-    worldart_animation_id: Option<&'a str>,
+    worldart_animation_id: Option<Cow<'a, str>>,
     /// Search for World Art IDs in the Movies section
     #[serde(skip_serializing_if = "Option::is_none")]
-    worldart_cinema_id: Option<&'a str>,
+    worldart_cinema_id: Option<Cow<'a, str>>,
     /// Search the full World Art link
     #[serde(skip_serializing_if = "Option::is_none")]
-    worldart_link: Option<&'a str>,
+    worldart_link: Option<Cow<'a, str>>,
     /// Search by Shikimori ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    shikimori_id: Option<&'a str>,
+    shikimori_id: Option<Cow<'a, str>>,
 
     /// Maximum number of outputs
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -78,40 +430,38 @@ pub struct SearchQuery<'a> {
 
     /// Filtering materials by their type. For your convenience, a large number of types of films and TV series are available. Required types are specified separated by commas
     #[serde(skip_serializing_if = "Option::is_none")]
-    types: Option<&'a [ReleaseType]>,
+    types: Option<Cow<'a, [ReleaseType]>>,
 
     ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
     #[serde(skip_serializing_if = "Option::is_none")]
-    year: Option<&'a [u32]>,
+    year: Option<Cow<'a, [Year]>>,
 
     /// Filtering materials by translation ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    translation_id: Option<&'a [u32]>,
+    translation_id: Option<Cow<'a, [u32]>>,
     /// Filter content by translation type. Allows you to output only voice translation or only subtitles
     #[serde(skip_serializing_if = "Option::is_none")]
-    translation_type: Option<&'a [TranslationType]>,
+    translation_type: Option<Cow<'a, [TranslationType]>>,
     /// Increases the priority of certain voices. The IDs are listed in commas. The "leftmost" ID, the higher its priority. IDs of all voices can be received through API resource /translations or on the page of list of voices. Standard priority of dubbed and prof. Multivoiced". To deactivate standard priority you need to pass value 0. You can also specify the translation type (subtitles/voice) instead of the ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    // TODO: Add wrapper
-    prioritize_translations: Option<&'a [&'a str]>,
+    prioritize_translations: Option<Cow<'a, [TranslationPriority]>>,
     /// Decreases the priority of certain voices. The IDs are listed in commas. The "leftmost" ID, the lower its priority. IDs of all voices can be received through API resource /translations or on page of voices list. Standard priority of soundtracks "Ukrainian", "English" and all subtitles are lowered. To deactivate standard priority you need to pass value 0. You can also specify the translation type (subtitles/voice) instead of the ID
     #[serde(skip_serializing_if = "Option::is_none")]
-    // TODO: Add wrapper
-    unprioritize_translations: Option<&'a [&'a str]>,
+    unprioritize_translations: Option<Cow<'a, [TranslationPriority]>>,
     /// Increases the priority of a certain type of translation. If you specify voice, voiceovers will be output first. If subtitles, subtitles will be output
     #[serde(skip_serializing_if = "Option::is_none")]
-    prioritize_translation_type: Option<&'a [TranslationType]>,
+    prioritize_translation_type: Option<Cow<'a, [TranslationType]>>,
 
     /// Filtering materials based on the presence of a specific field. Materials that have at least one of the listed fields are shown. In order to show only materials that have all the listed fields
     #[serde(skip_serializing_if = "Option::is_none")]
-    has_field: Option<&'a [MaterialDataField]>,
+    has_field: Option<Cow<'a, [MaterialDataField]>>,
     /// Filtering materials based on the presence of a specific field. Materials that have all the listed fields are shown
     #[serde(skip_serializing_if = "Option::is_none")]
-    has_field_and: Option<&'a [MaterialDataField]>,
+    has_field_and: Option<Cow<'a, [MaterialDataField]>>,
 
     /// Deletes certain voices from the search results. IDs are listed separated by commas
     #[serde(skip_serializing_if = "Option::is_none")]
-    block_translations: Option<&'a [u32]>,
+    block_translations: Option<Cow<'a, [u32]>>,
 
     /// Filtering materials by camrip parameter. If you specify false, only materials with a quality picture will be output. If you don't specify this parameter, all materials will be displayed
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -126,7 +476,7 @@ pub struct SearchQuery<'a> {
 
     /// With this option you can specify which season you are interested in. This way, only shows that have that season will appear in the search results. Passing this parameter also automatically enables the with_seasons parameter
     #[serde(skip_serializing_if = "Option::is_none")]
-    season: Option<&'a [u32]>,
+    season: Option<Cow<'a, [u32]>>,
 
     /// If you specify true, the seasons field will be added to each series (even if with_seasons is not specified or specified as false) and the episodes field with the episodes of that season will be added to each season. If the with_episodes parameter is used, the series numbers will correspond to the normal series references. If you use the with_episodes_data parameter, episode objects will be assigned to the episode numbers, where the link will be available via the link parameter, the episode name (if any) via the title parameter, and the frames via screenshots
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -137,18 +487,25 @@ pub struct SearchQuery<'a> {
 
     /// With this option, you can specify which episode of a particular season you are interested in. Thus, only shows with that episode will appear in the search results. If you use this parameter, you must also pass the season parameter. Passing this parameter also automatically includes the with_episodes parameter
     #[serde(skip_serializing_if = "Option::is_none")]
-    episode: Option<&'a [u32]>,
+    episode: Option<Cow<'a, [u32]>>,
 
     /// If you specify true, all links to players will be replaced by special links to pages with players (suitable for cases when you don't have your own site). You can customize appearance of these pages in settings in the base. If parameter with_seasons or with_episodes / with_episodes_data is specified together with this parameter, links in seasons and episodes will also be replaced
     #[serde(skip_serializing_if = "Option::is_none")]
     with_page_links: Option<bool>,
 
+    /// If you specify false, frames are not added to the materials, which reduces the size of the response. The `screenshots` of the releases are empty then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    with_screenshots: Option<bool>,
+    /// If you specify false, frames are not added to the episode objects of `with_episodes_data`. The `screenshots` of the episodes are empty then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    with_episode_screenshots: Option<bool>,
+
     /// Filters materials by country in which they should not be blocked. The country codes are specified separated by commas
     #[serde(skip_serializing_if = "Option::is_none")]
-    not_blocked_in: Option<&'a [&'a str]>,
+    not_blocked_in: Option<Cow<'a, [CountryCode]>>,
     /// A simpler analog of the previous parameter. Our server itself checks which country the current request comes from and doesn't display those materials that are blocked for that country. This parameter can be useful if the API is called on your site
     #[serde(skip_serializing_if = "Option::is_none")]
-    not_blocked_for_me: Option<&'a [&'a str]>,
+    not_blocked_for_me: Option<bool>,
 
     /// If you specify true, the material_data field will be added to each movie/series with information from Kinopoisk and Shikimori
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -156,95 +513,103 @@ pub struct SearchQuery<'a> {
 
     /// Filtering materials by country. You can specify a single value or multiple values, separated by commas (then materials with at least one of the listed countries will be displayed). The parameter is case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    countries: Option<&'a [&'a str]>,
+    countries: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    genres: Option<&'a [&'a str]>,
+    genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_genres: Option<&'a [&'a str]>,
+    anime_genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    drama_genres: Option<&'a [&'a str]>,
+    drama_genres: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    all_genres: Option<&'a [&'a str]>,
+    all_genres: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by duration (in minutes). You can specify either a single value to search for the exact duration, or an interval.
     #[serde(skip_serializing_if = "Option::is_none")]
-    duration: Option<&'a [&'a str]>,
+    duration: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    kinopoisk_rating: Option<&'a [&'a str]>,
+    kinopoisk_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    imdb_rating: Option<&'a [&'a str]>,
+    imdb_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    shikimori_rating: Option<&'a [&'a str]>,
+    shikimori_rating: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     #[serde(skip_serializing_if = "Option::is_none")]
-    mydramalist_rating: Option<&'a [&'a str]>,
+    mydramalist_rating: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    actors: Option<&'a [&'a str]>,
+    actors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    directors: Option<&'a [&'a str]>,
+    directors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    producers: Option<&'a [&'a str]>,
+    producers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    writers: Option<&'a [&'a str]>,
+    writers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    composers: Option<&'a [&'a str]>,
+    composers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    editors: Option<&'a [&'a str]>,
+    editors: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    designers: Option<&'a [&'a str]>,
+    designers: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
     #[serde(skip_serializing_if = "Option::is_none")]
-    operators: Option<&'a [&'a str]>,
+    operators: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by age rating. You can specify a single value or multiple values, separated by commas. The parameter is case-insensitive
     #[serde(skip_serializing_if = "Option::is_none")]
-    rating_mpaa: Option<&'a [MppaRating]>,
+    rating_mpaa: Option<Cow<'a, [MppaRating]>>,
 
     /// Filter content by the minimum age from which it can be viewed. You can specify either a single value or a range of values
     #[serde(skip_serializing_if = "Option::is_none")]
-    minimal_age: Option<&'a [&'a str]>,
+    minimal_age: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filtering materials by anime type. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_kind: Option<&'a [AnimeKind]>,
+    anime_kind: Option<Cow<'a, [AnimeKind]>>,
 
     /// Filters materials by MyDramaList tags. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    mydramalist_tags: Option<&'a [&'a str]>,
+    mydramalist_tags: Option<Cow<'a, [Cow<'a, str>]>>,
 
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_status: Option<&'a [AnimeStatus]>,
+    anime_status: Option<Cow<'a, [AnimeStatus]>>,
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    drama_status: Option<&'a [DramaStatus]>,
+    drama_status: Option<Cow<'a, [DramaStatus]>>,
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    all_status: Option<&'a [AllStatus]>,
+    all_status: Option<Cow<'a, [AllStatus]>>,
 
     /// Filtering materials by anime studio. You can specify either one value or several values separated by commas (then materials with at least one of the listed studios will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_studios: Option<&'a [&'a str]>,
+    anime_studios: Option<Cow<'a, [Cow<'a, str>]>>,
     /// Filtering materials by license owner. You can specify a single value or several values separated by commas (then materials that have at least one of the listed owners will be displayed)
     #[serde(skip_serializing_if = "Option::is_none")]
-    anime_licensed_by: Option<&'a [&'a str]>,
+    anime_licensed_by: Option<Cow<'a, [Cow<'a, str>]>>,
+
+    /// Value of the `Accept-Language` header for this query, overrides the client one
+    #[serde(skip)]
+    accept_language: Option<Cow<'a, str>>,
+
+    /// Client bound by [`Client::search`], used by [`SearchQuery::send`]
+    #[serde(skip)]
+    client: Option<Client>,
 }
 
 impl<'a> SearchQuery<'a> {
@@ -282,6 +647,8 @@ impl<'a> SearchQuery<'a> {
             with_episodes_data: None,
             episode: None,
             with_page_links: None,
+            with_screenshots: None,
+            with_episode_screenshots: None,
             not_blocked_in: None,
             not_blocked_for_me: None,
             with_material_data: None,
@@ -312,17 +679,22 @@ impl<'a> SearchQuery<'a> {
             all_status: None,
             anime_studios: None,
             anime_licensed_by: None,
+            accept_language: None,
+            client: None,
         }
     }
 
     /// The name of the movie. It is not necessary to specify it explicitly, you can use a variant written by the user or a variant containing extra words. If you specify one of these parameters, the search will be performed on several fields at once: `title`, `title_orig`, `other_title`
-    pub fn with_title<'b>(&'b mut self, title: &'a str) -> &'b mut SearchQuery<'a> {
-        self.title = Some(title);
+    pub fn with_title<'b>(&'b mut self, title: impl Into<Cow<'a, str>>) -> &'b mut SearchQuery<'a> {
+        self.title = Some(title.into());
         self
     }
     /// Original title. When this option is used, only the title_orig will be searched. It is not necessary to specify it explicitly, you can use a variant written by the user or a variant containing extra words
-    pub fn with_title_orig<'b>(&'b mut self, title_orig: &'a str) -> &'b mut SearchQuery<'a> {
-        self.title_orig = Some(title_orig);
+    pub fn with_title_orig<'b>(
+        &'b mut self,
+        title_orig: impl Into<Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.title_orig = Some(title_orig.into());
         self
     }
     /// If title or title_orig parameter was specified, this parameter defines the severity of checking if the title of the material corresponds to the search query. If true, the search results will show only those materials in which the word order is exactly the same as in the search query (but extra words in the search query are still allowed)
@@ -336,57 +708,83 @@ impl<'a> SearchQuery<'a> {
         self
     }
 
-    /// Search by Kodik ID
-    pub fn with_id<'b>(&'b mut self, id: &'a str) -> &'b mut SearchQuery<'a> {
-        self.id = Some(id);
+    /// Search by Kodik ID. Replaces the IDs set by `with_ids`
+    pub fn with_id<'b>(&'b mut self, id: impl Into<Cow<'a, str>>) -> &'b mut SearchQuery<'a> {
+        self.id = Some(Cow::Owned(vec![id.into()]));
+        self
+    }
+    /// Search by several Kodik IDs at once, materials with any of the IDs are shown. Replaces the ID set by `with_id`. An empty list is rejected by `validate`
+    pub fn with_ids<'b>(
+        &'b mut self,
+        ids: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.id = Some(ids.into_query_list());
         self
     }
     /// Search for any link to the player
-    pub fn with_player_link<'b>(&'b mut self, player_link: &'a str) -> &'b mut SearchQuery<'a> {
-        self.player_link = Some(player_link);
+    pub fn with_player_link<'b>(
+        &'b mut self,
+        player_link: impl Into<Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.player_link = Some(player_link.into());
         self
     }
 
     /// Search by kinopoisk ID
-    pub fn with_kinopoisk_id<'b>(&'b mut self, kinopoisk_id: &'a str) -> &'b mut SearchQuery<'a> {
-        self.kinopoisk_id = Some(kinopoisk_id);
+    pub fn with_kinopoisk_id<'b>(
+        &'b mut self,
+        kinopoisk_id: impl Into<Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.kinopoisk_id = Some(kinopoisk_id.into());
         self
     }
     /// Search by IMDb ID
-    pub fn with_imdb_id<'b>(&'b mut self, imdb_id: &'a str) -> &'b mut SearchQuery<'a> {
-        self.imdb_id = Some(imdb_id);
+    pub fn with_imdb_id<'b>(
+        &'b mut self,
+        imdb_id: impl Into<Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.imdb_id = Some(imdb_id.into());
         self
     }
     /// Search by MyDramaList ID
-    pub fn with_mdl_id<'b>(&'b mut self, mdl_id: &'a str) -> &'b mut SearchQuery<'a> {
-        self.mdl_id = Some(mdl_id);
+    pub fn with_mdl_id<'b>(
+        &'b mut self,
+        mdl_id: impl Into<Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.mdl_id = Some(mdl_id.into());
         self
     }
 
     /// Search for World Art IDs in the anime section (World Art has different content sections, each with their own independent IDs)
     pub fn with_worldart_animation_id<'b>(
         &'b mut self,
-        worldart_animation_id: &'a str,
+        worldart_animation_id: impl Into<Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.worldart_animation_id = Some(worldart_animation_id);
+        self.worldart_animation_id = Some(worldart_animation_id.into());
         self
     }
     /// Search for World Art IDs in the Movies section
     pub fn with_worldart_cinema_id<'b>(
         &'b mut self,
-        worldart_cinema_id: &'a str,
+        worldart_cinema_id: impl Into<Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.worldart_cinema_id = Some(worldart_cinema_id);
+        self.worldart_cinema_id = Some(worldart_cinema_id.into());
         self
     }
     /// Search the full World Art link
-    pub fn with_worldart_link<'b>(&'b mut self, worldart_link: &'a str) -> &'b mut SearchQuery<'a> {
-        self.worldart_link = Some(worldart_link);
+    pub fn with_worldart_link<'b>(
+        &'b mut self,
+        worldart_link: impl Into<Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.worldart_link = Some(worldart_link.into());
         self
     }
     /// Search by Shikimori ID
-    pub fn with_shikimori_id<'b>(&'b mut self, shikimori_id: &'a str) -> &'b mut SearchQuery<'a> {
-        self.shikimori_id = Some(shikimori_id);
+    pub fn with_shikimori_id<'b>(
+        &'b mut self,
+        shikimori_id: impl Into<Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.shikimori_id = Some(shikimori_id.into());
         self
     }
 
@@ -397,84 +795,94 @@ impl<'a> SearchQuery<'a> {
     }
 
     /// Filtering materials by their type. For your convenience, a large number of types of films and TV series are available. Required types are specified separated by commas
-    pub fn with_types<'b>(&'b mut self, types: &'a [ReleaseType]) -> &'b mut SearchQuery<'a> {
-        self.types = Some(types);
+    pub fn with_types<'b>(
+        &'b mut self,
+        types: impl IntoQueryList<'a, ReleaseType>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.types = Some(ReleaseType::known_only(types.into_query_list()));
+        self
+    }
+
+    /// Filter materials by categories, expanded to the underlying release types. Replaces the types set by `with_types`
+    pub fn with_category<'b>(&'b mut self, categories: &[Category]) -> &'b mut SearchQuery<'a> {
+        self.types = Some(Cow::Owned(Category::release_types_of(categories)));
         self
     }
 
     ///Filter materials by year If you set this parameter, only materials of the corresponding year will be displayed
-    pub fn with_year<'b>(&'b mut self, year: &'a [u32]) -> &'b mut SearchQuery<'a> {
-        self.year = Some(year);
+    pub fn with_year<'b>(
+        &'b mut self,
+        year: impl IntoQueryList<'a, Year>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.year = Some(year.into_query_list());
         self
     }
 
     /// Filtering materials by translation ID
     pub fn with_translation_id<'b>(
         &'b mut self,
-        translation_id: &'a [u32],
+        translation_id: impl IntoQueryList<'a, u32>,
     ) -> &'b mut SearchQuery<'a> {
-        self.translation_id = Some(translation_id);
+        self.translation_id = Some(translation_id.into_query_list());
         self
     }
     /// Filter content by translation type. Allows you to output only voice translation or only subtitles
     pub fn with_translation_type<'b>(
         &'b mut self,
-        translation_type: &'a [TranslationType],
+        translation_type: impl IntoQueryList<'a, TranslationType>,
     ) -> &'b mut SearchQuery<'a> {
-        self.translation_type = Some(translation_type);
+        self.translation_type = Some(translation_type.into_query_list());
         self
     }
 
     /// Filtering materials based on the presence of a specific field. Materials that have at least one of the listed fields are shown. In order to show only materials that have all the listed fields
     pub fn with_has_field<'b>(
         &'b mut self,
-        has_field: &'a [MaterialDataField],
+        has_field: impl IntoQueryList<'a, MaterialDataField>,
     ) -> &'b mut SearchQuery<'a> {
-        self.has_field = Some(has_field);
+        self.has_field = Some(has_field.into_query_list());
         self
     }
     /// Filtering materials based on the presence of a specific field. Materials that have all the listed fields are shown
     pub fn with_has_field_and<'b>(
         &'b mut self,
-        has_field: &'a [MaterialDataField],
+        has_field: impl IntoQueryList<'a, MaterialDataField>,
     ) -> &'b mut SearchQuery<'a> {
-        self.has_field_and = Some(has_field);
+        self.has_field_and = Some(has_field.into_query_list());
         self
     }
 
     /// Increases the priority of certain voices. The IDs are listed in commas. The "leftmost" ID, the higher its priority. IDs of all voices can be received through API resource /translations or on the page of list of voices. Standard priority of dubbed and prof. Multivoiced". To deactivate standard priority you need to pass value 0. You can also specify the translation type (subtitles/voice) instead of the ID
-    // TODO: Add wrapper
     pub fn with_prioritize_translations<'b>(
         &'b mut self,
-        prioritize_translations: &'a [&'a str],
+        prioritize_translations: impl IntoQueryList<'a, TranslationPriority>,
     ) -> &'b mut SearchQuery<'a> {
-        self.prioritize_translations = Some(prioritize_translations);
+        self.prioritize_translations = Some(prioritize_translations.into_query_list());
         self
     }
     /// Decreases the priority of certain voices. The IDs are listed in commas. The "leftmost" ID, the lower its priority. IDs of all voices can be received through API resource /translations or on page of voices list. Standard priority of soundtracks "Ukrainian", "English" and all subtitles are lowered. To deactivate standard priority you need to pass value 0. You can also specify the translation type (subtitles/voice) instead of the ID
-    // TODO: Add wrapper
     pub fn with_unprioritize_translations<'b>(
         &'b mut self,
-        unprioritize_translations: &'a [&'a str],
+        unprioritize_translations: impl IntoQueryList<'a, TranslationPriority>,
     ) -> &'b mut SearchQuery<'a> {
-        self.unprioritize_translations = Some(unprioritize_translations);
+        self.unprioritize_translations = Some(unprioritize_translations.into_query_list());
         self
     }
     /// Increases the priority of a certain type of translation. If you specify voice, voiceovers will be output first. If subtitles, subtitles will be output
     pub fn with_prioritize_translation_type<'b>(
         &'b mut self,
-        prioritize_translation_type: &'a [TranslationType],
+        prioritize_translation_type: impl IntoQueryList<'a, TranslationType>,
     ) -> &'b mut SearchQuery<'a> {
-        self.prioritize_translation_type = Some(prioritize_translation_type);
+        self.prioritize_translation_type = Some(prioritize_translation_type.into_query_list());
         self
     }
 
     /// Deletes certain voices from the search results. IDs are listed separated by commas
     pub fn with_block_translations<'b>(
         &'b mut self,
-        block_translations: &'a [u32],
+        block_translations: impl IntoQueryList<'a, u32>,
     ) -> &'b mut SearchQuery<'a> {
-        self.block_translations = Some(block_translations);
+        self.block_translations = Some(block_translations.into_query_list());
         self
     }
 
@@ -496,8 +904,11 @@ impl<'a> SearchQuery<'a> {
     }
 
     /// With this option you can specify which season you are interested in. This way, only shows that have that season will appear in the search results. Passing this parameter also automatically enables the with_seasons parameter
-    pub fn with_season<'b>(&'b mut self, season: &'a [u32]) -> &'b mut SearchQuery<'a> {
-        self.season = Some(season);
+    pub fn with_season<'b>(
+        &'b mut self,
+        season: impl IntoQueryList<'a, u32>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.season = Some(season.into_query_list());
         self
     }
 
@@ -516,8 +927,11 @@ impl<'a> SearchQuery<'a> {
     }
 
     /// With this option, you can specify which episode of a particular season you are interested in. Thus, only shows with that episode will appear in the search results. If you use this parameter, you must also pass the season parameter. Passing this parameter also automatically includes the with_episodes parameter
-    pub fn with_episode<'b>(&'b mut self, episode: &'a [u32]) -> &'b mut SearchQuery<'a> {
-        self.episode = Some(episode);
+    pub fn with_episode<'b>(
+        &'b mut self,
+        episode: impl IntoQueryList<'a, u32>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.episode = Some(episode.into_query_list());
         self
     }
 
@@ -527,23 +941,39 @@ impl<'a> SearchQuery<'a> {
         self
     }
 
+    /// If you specify false, frames are not added to the materials, which reduces the size of the response. The `screenshots` of the releases are empty then
+    pub fn with_screenshots<'b>(&'b mut self, with_screenshots: bool) -> &'b mut SearchQuery<'a> {
+        self.with_screenshots = Some(with_screenshots);
+        self
+    }
+    /// If you specify false, frames are not added to the episode objects of `with_episodes_data`. The `screenshots` of the episodes are empty then
+    pub fn with_episode_screenshots<'b>(
+        &'b mut self,
+        with_episode_screenshots: bool,
+    ) -> &'b mut SearchQuery<'a> {
+        self.with_episode_screenshots = Some(with_episode_screenshots);
+        self
+    }
+
     /// Filters materials by country in which they should not be blocked. The country codes are specified separated by commas
     pub fn with_not_blocked_in<'b>(
         &'b mut self,
-        not_blocked_in: &'a [&'a str],
+        not_blocked_in: impl IntoQueryList<'a, CountryCode>,
     ) -> &'b mut SearchQuery<'a> {
-        self.not_blocked_in = Some(not_blocked_in);
+        self.not_blocked_in = Some(not_blocked_in.into_query_list());
         self
     }
     /// A simpler analog of the previous parameter. Our server itself checks which country the current request comes from and doesn't display those materials that are blocked for that country. This parameter can be useful if the API is called on your site
     pub fn with_not_blocked_for_me<'b>(
         &'b mut self,
-        not_blocked_for_me: &'a [&'a str],
+        not_blocked_for_me: bool,
     ) -> &'b mut SearchQuery<'a> {
         self.not_blocked_for_me = Some(not_blocked_for_me);
         self
     }
     /// If you specify true, the material_data field will be added to each movie/series with information from Kinopoisk and Shikimori
+    ///
+    /// If not set, it is enabled automatically when a filter by the material data is used: countries, genres, duration, ratings, personas, `rating_mpaa`, `minimal_age`, `anime_kind`, `mydramalist_tags`, statuses, `anime_studios` or `anime_licensed_by`. Set it to false explicitly to opt out.
     pub fn with_material_data<'b>(
         &'b mut self,
         with_material_data: bool,
@@ -553,217 +983,602 @@ impl<'a> SearchQuery<'a> {
     }
 
     /// Filtering materials by country. You can specify a single value or multiple values, separated by commas (then materials with at least one of the listed countries will be displayed). The parameter is case sensitive
-    pub fn with_countries<'b>(&'b mut self, countries: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.countries = Some(countries);
+    pub fn with_countries<'b>(
+        &'b mut self,
+        countries: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.countries = Some(countries.into_query_list());
         self
     }
 
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
-    pub fn with_genres<'b>(&'b mut self, genres: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.genres = Some(genres);
+    pub fn with_genres<'b>(
+        &'b mut self,
+        genres: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.genres = Some(genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     pub fn with_anime_genres<'b>(
         &'b mut self,
-        anime_genres: &'a [&'a str],
+        anime_genres: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.anime_genres = Some(anime_genres);
+        self.anime_genres = Some(anime_genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
     pub fn with_drama_genres<'b>(
         &'b mut self,
-        drama_genres: &'a [&'a str],
+        drama_genres: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.drama_genres = Some(drama_genres);
+        self.drama_genres = Some(drama_genres.into_query_list());
         self
     }
     /// Filtering by genre. You can specify either one value or several values separated by commas (then materials that have at least one of the specified genres will be displayed). You can search by Kinopoisk, Shikimori, MyDramaList or by all genres at once. The parameter is not case sensitive
-    pub fn with_all_genres<'b>(&'b mut self, all_genres: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.all_genres = Some(all_genres);
+    pub fn with_all_genres<'b>(
+        &'b mut self,
+        all_genres: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.all_genres = Some(all_genres.into_query_list());
         self
     }
 
     /// Filtering by duration (in minutes). You can specify either a single value to search for the exact duration, or an interval.
-    pub fn with_duration<'b>(&'b mut self, duration: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.duration = Some(duration);
+    pub fn with_duration<'b>(
+        &'b mut self,
+        duration: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.duration = Some(duration.into_query_list());
+        self
+    }
+    /// Same as `with_duration`, but with a typed exact value or interval
+    pub fn with_duration_range<'b>(
+        &'b mut self,
+        duration: DurationRange,
+    ) -> &'b mut SearchQuery<'a> {
+        self.duration = Some(range_query_list(duration));
         self
     }
 
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_kinopoisk_rating<'b>(
         &'b mut self,
-        kinopoisk_rating: &'a [&'a str],
+        kinopoisk_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.kinopoisk_rating = Some(kinopoisk_rating);
+        self.kinopoisk_rating = Some(kinopoisk_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_imdb_rating<'b>(
         &'b mut self,
-        imdb_rating: &'a [&'a str],
+        imdb_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.imdb_rating = Some(imdb_rating);
+        self.imdb_rating = Some(imdb_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_shikimori_rating<'b>(
         &'b mut self,
-        shikimori_rating: &'a [&'a str],
+        shikimori_rating: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.shikimori_rating = Some(shikimori_rating);
+        self.shikimori_rating = Some(shikimori_rating.into_query_list());
         self
     }
     /// Filtering by Kinopoisk, IMDb, Shikimori, or MyDramaList ratings. You can specify either a single value to search for the exact rating, or an interval
     pub fn with_mydramalist_rating<'b>(
         &'b mut self,
-        mydramalist_rating: &'a [&'a str],
+        mydramalist_rating: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.mydramalist_rating = Some(mydramalist_rating.into_query_list());
+        self
+    }
+    /// Same as `with_kinopoisk_rating`, but with a typed exact value or interval
+    pub fn with_kinopoisk_rating_range<'b>(
+        &'b mut self,
+        kinopoisk_rating: Range,
     ) -> &'b mut SearchQuery<'a> {
-        self.mydramalist_rating = Some(mydramalist_rating);
+        self.kinopoisk_rating = Some(range_query_list(kinopoisk_rating));
+        self
+    }
+    /// Same as `with_imdb_rating`, but with a typed exact value or interval
+    pub fn with_imdb_rating_range<'b>(&'b mut self, imdb_rating: Range) -> &'b mut SearchQuery<'a> {
+        self.imdb_rating = Some(range_query_list(imdb_rating));
+        self
+    }
+    /// Same as `with_shikimori_rating`, but with a typed exact value or interval
+    pub fn with_shikimori_rating_range<'b>(
+        &'b mut self,
+        shikimori_rating: Range,
+    ) -> &'b mut SearchQuery<'a> {
+        self.shikimori_rating = Some(range_query_list(shikimori_rating));
+        self
+    }
+    /// Same as `with_mydramalist_rating`, but with a typed exact value or interval
+    pub fn with_mydramalist_rating_range<'b>(
+        &'b mut self,
+        mydramalist_rating: Range,
+    ) -> &'b mut SearchQuery<'a> {
+        self.mydramalist_rating = Some(range_query_list(mydramalist_rating));
         self
     }
 
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_actors<'b>(&'b mut self, actors: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.actors = Some(actors);
+    pub fn with_actors<'b>(
+        &'b mut self,
+        actors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.actors = Some(actors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_directors<'b>(&'b mut self, directors: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.directors = Some(directors);
+    pub fn with_directors<'b>(
+        &'b mut self,
+        directors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.directors = Some(directors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_producers<'b>(&'b mut self, producers: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.producers = Some(producers);
+    pub fn with_producers<'b>(
+        &'b mut self,
+        producers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.producers = Some(producers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_writers<'b>(&'b mut self, writers: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.writers = Some(writers);
+    pub fn with_writers<'b>(
+        &'b mut self,
+        writers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.writers = Some(writers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_composers<'b>(&'b mut self, composers: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.composers = Some(composers);
+    pub fn with_composers<'b>(
+        &'b mut self,
+        composers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.composers = Some(composers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_editors<'b>(&'b mut self, editors: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.editors = Some(editors);
+    pub fn with_editors<'b>(
+        &'b mut self,
+        editors: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.editors = Some(editors.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_designers<'b>(&'b mut self, designers: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.designers = Some(designers);
+    pub fn with_designers<'b>(
+        &'b mut self,
+        designers: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.designers = Some(designers.into_query_list());
         self
     }
     /// Filtering materials by personas. You can specify a single value or multiple values, separated by commas (then materials that have at least one of the specified personas will be displayed). This parameter is case-independent. You can specify filters for several professions at once
-    pub fn with_operators<'b>(&'b mut self, operators: &'a [&'a str]) -> &'b mut SearchQuery<'a> {
-        self.operators = Some(operators);
+    pub fn with_operators<'b>(
+        &'b mut self,
+        operators: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.operators = Some(operators.into_query_list());
         self
     }
 
     /// Filtering materials by age rating. You can specify a single value or multiple values, separated by commas. The parameter is case-insensitive
     pub fn with_rating_mpaa<'b>(
         &'b mut self,
-        rating_mpaa: &'a [MppaRating],
+        rating_mpaa: impl IntoQueryList<'a, MppaRating>,
     ) -> &'b mut SearchQuery<'a> {
-        self.rating_mpaa = Some(rating_mpaa);
+        self.rating_mpaa = Some(rating_mpaa.into_query_list());
         self
     }
 
     /// Filter content by the minimum age from which it can be viewed. You can specify either a single value or a range of values
     pub fn with_minimal_age<'b>(
         &'b mut self,
-        minimal_age: &'a [&'a str],
+        minimal_age: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.minimal_age = Some(minimal_age);
+        self.minimal_age = Some(minimal_age.into_query_list());
         self
     }
 
     /// Filtering materials by anime type. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     pub fn with_anime_kind<'b>(
         &'b mut self,
-        anime_kind: &'a [AnimeKind],
+        anime_kind: impl IntoQueryList<'a, AnimeKind>,
     ) -> &'b mut SearchQuery<'a> {
-        self.anime_kind = Some(anime_kind);
+        self.anime_kind = Some(anime_kind.into_query_list());
         self
     }
 
     /// Filters materials by MyDramaList tags. You can specify one value or several values separated by commas (then materials with at least one of these types will be displayed)
     pub fn with_mydramalist_tags<'b>(
         &'b mut self,
-        mydramalist_tags: &'a [&'a str],
+        mydramalist_tags: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.mydramalist_tags = Some(mydramalist_tags);
+        self.mydramalist_tags = Some(mydramalist_tags.into_query_list());
         self
     }
 
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_anime_status<'b>(
         &'b mut self,
-        anime_status: &'a [AnimeStatus],
+        anime_status: impl IntoQueryList<'a, AnimeStatus>,
     ) -> &'b mut SearchQuery<'a> {
-        self.anime_status = Some(anime_status);
+        self.anime_status = Some(anime_status.into_query_list());
         self
     }
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_drama_status<'b>(
         &'b mut self,
-        drama_status: &'a [DramaStatus],
+        drama_status: impl IntoQueryList<'a, DramaStatus>,
     ) -> &'b mut SearchQuery<'a> {
-        self.drama_status = Some(drama_status);
+        self.drama_status = Some(drama_status.into_query_list());
         self
     }
     /// Filter materials by Shikimori status, MyDramaList, or by all statuses. You can specify a single value or several values separated by commas (then materials that have at least one of the listed statuses will be displayed)
     pub fn with_all_status<'b>(
         &'b mut self,
-        all_status: &'a [AllStatus],
+        all_status: impl IntoQueryList<'a, AllStatus>,
     ) -> &'b mut SearchQuery<'a> {
-        self.all_status = Some(all_status);
+        self.all_status = Some(all_status.into_query_list());
         self
     }
 
     /// Filtering materials by anime studio. You can specify either one value or several values separated by commas (then materials with at least one of the listed studios will be displayed)
     pub fn with_anime_studios<'b>(
         &'b mut self,
-        anime_studios: &'a [&'a str],
+        anime_studios: impl IntoQueryList<'a, Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.anime_studios = Some(anime_studios);
+        self.anime_studios = Some(anime_studios.into_query_list());
         self
     }
     /// Filtering materials by license owner. You can specify a single value or several values separated by commas (then materials that have at least one of the listed owners will be displayed)
     pub fn with_anime_licensed_by<'b>(
         &'b mut self,
-        anime_licensed_by: &'a [&'a str],
+        anime_licensed_by: impl IntoQueryList<'a, Cow<'a, str>>,
+    ) -> &'b mut SearchQuery<'a> {
+        self.anime_licensed_by = Some(anime_licensed_by.into_query_list());
+        self
+    }
+
+    /// Value of the `Accept-Language` header for this query, overrides the one set in [`crate::ClientBuilder::accept_language`]
+    pub fn with_accept_language<'b>(
+        &'b mut self,
+        accept_language: impl Into<Cow<'a, str>>,
     ) -> &'b mut SearchQuery<'a> {
-        self.anime_licensed_by = Some(anime_licensed_by);
+        self.accept_language = Some(accept_language.into());
         self
     }
 
+    /// Query parameters for the hosted pages mode
+    ///
+    /// Same as the parameters of this query, but with `with_page_links` enabled, so all links in the response lead to pages with players hosted by Kodik (see [`Release::page_url`]). Appearance of these pages is configured in the Kodik base settings and not by request parameters.
+    pub fn hosted_page_params(&self) -> Result<Vec<(String, String)>, Error> {
+        reject_commas_in_lists(self)?;
+
+        let mut query = self.clone();
+
+        query.with_page_links(true);
+
+        query.query_parts()
+    }
+
+    /// Checks the query for combinations of parameters the API doesn't accept, without sending a request
+    ///
+    /// Called by `execute` and `stream` first. Rejected with [`Error::InvalidQuery`] are:
+    /// - `strict` or `full_match` without a non-empty `title` or `title_orig`
+    /// - `episode` without `season`
+    /// - `limit` of `0`
+    /// - empty list of IDs set by `with_ids`
+    /// - `with_types` without known release types
+    ///
+    /// A list value containing a comma is rejected with [`Error::InvalidParameter`].
+    pub fn validate(&self) -> Result<(), Error> {
+        let has_title = [&self.title, &self.title_orig]
+            .into_iter()
+            .flatten()
+            .any(|title| !title.trim().is_empty());
+
+        if !has_title && self.strict == Some(true) {
+            return Err(Error::InvalidQuery(
+                "`strict` has an effect only together with `title` or `title_orig`".to_owned(),
+            ));
+        }
+
+        if !has_title && self.full_match == Some(true) {
+            return Err(Error::InvalidQuery(
+                "`full_match` has an effect only together with `title` or `title_orig`".to_owned(),
+            ));
+        }
+
+        if self.episode.is_some() && self.season.is_none() {
+            return Err(Error::InvalidQuery(
+                "`episode` requires `season` to be set".to_owned(),
+            ));
+        }
+
+        if self.limit == Some(0) {
+            return Err(Error::InvalidQuery(
+                "`limit` must be greater than 0".to_owned(),
+            ));
+        }
+
+        ReleaseType::validate_query_types(self.types.as_deref())?;
+
+        reject_commas_in_lists(self)?;
+
+        if self.id.as_ref().is_some_and(|ids| ids.is_empty()) {
+            return Err(Error::InvalidQuery("`id` must not be empty".to_owned()));
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the query, enabling `with_material_data` if a filter by the material data is used and it is not set explicitly
+    fn query_parts(&self) -> Result<Vec<(String, String)>, Error> {
+        if self.with_material_data.is_some() || !self.has_material_data_filters() {
+            return serialize_into_query_parts(self);
+        }
+
+        let mut query = self.clone();
+
+        query.with_material_data(true);
+
+        serialize_into_query_parts(&query)
+    }
+
+    fn has_material_data_filters(&self) -> bool {
+        self.countries.is_some()
+            || self.genres.is_some()
+            || self.anime_genres.is_some()
+            || self.drama_genres.is_some()
+            || self.all_genres.is_some()
+            || self.duration.is_some()
+            || self.kinopoisk_rating.is_some()
+            || self.imdb_rating.is_some()
+            || self.shikimori_rating.is_some()
+            || self.mydramalist_rating.is_some()
+            || self.actors.is_some()
+            || self.directors.is_some()
+            || self.producers.is_some()
+            || self.writers.is_some()
+            || self.composers.is_some()
+            || self.editors.is_some()
+            || self.designers.is_some()
+            || self.operators.is_some()
+            || self.rating_mpaa.is_some()
+            || self.minimal_age.is_some()
+            || self.anime_kind.is_some()
+            || self.mydramalist_tags.is_some()
+            || self.anime_status.is_some()
+            || self.drama_status.is_some()
+            || self.all_status.is_some()
+            || self.anime_studios.is_some()
+            || self.anime_licensed_by.is_some()
+    }
+
+    /// Converts the query into one that owns all its values, so that it can be stored or moved to another task
+    ///
+    /// ```
+    /// use kodik_api::search::SearchQuery;
+    ///
+    /// fn get_query(title: &str) -> SearchQuery<'static> {
+    ///     SearchQuery::new().with_title(title).clone().into_owned()
+    /// }
+    /// ```
+    pub fn into_owned(self) -> SearchQuery<'static> {
+        SearchQuery {
+            title: into_owned_str(self.title),
+            title_orig: into_owned_str(self.title_orig),
+            strict: self.strict,
+            full_match: self.full_match,
+            id: into_owned_str_list(self.id),
+            player_link: into_owned_str(self.player_link),
+            kinopoisk_id: into_owned_str(self.kinopoisk_id),
+            imdb_id: into_owned_str(self.imdb_id),
+            mdl_id: into_owned_str(self.mdl_id),
+            worldart_animation_id: into_owned_str(self.worldart_animation_id),
+            worldart_cinema_id: into_owned_str(self.worldart_cinema_id),
+            worldart_link: into_owned_str(self.worldart_link),
+            shikimori_id: into_owned_str(self.shikimori_id),
+            limit: self.limit,
+            types: into_owned_list(self.types),
+            year: into_owned_list(self.year),
+            translation_id: into_owned_list(self.translation_id),
+            translation_type: into_owned_list(self.translation_type),
+            prioritize_translations: into_owned_list(self.prioritize_translations),
+            unprioritize_translations: into_owned_list(self.unprioritize_translations),
+            prioritize_translation_type: into_owned_list(self.prioritize_translation_type),
+            has_field: into_owned_list(self.has_field),
+            has_field_and: into_owned_list(self.has_field_and),
+            block_translations: into_owned_list(self.block_translations),
+            camrip: self.camrip,
+            lgbt: self.lgbt,
+            with_seasons: self.with_seasons,
+            season: into_owned_list(self.season),
+            with_episodes: self.with_episodes,
+            with_episodes_data: self.with_episodes_data,
+            episode: into_owned_list(self.episode),
+            with_page_links: self.with_page_links,
+            with_screenshots: self.with_screenshots,
+            with_episode_screenshots: self.with_episode_screenshots,
+            not_blocked_in: into_owned_list(self.not_blocked_in),
+            not_blocked_for_me: self.not_blocked_for_me,
+            with_material_data: self.with_material_data,
+            countries: into_owned_str_list(self.countries),
+            genres: into_owned_str_list(self.genres),
+            anime_genres: into_owned_str_list(self.anime_genres),
+            drama_genres: into_owned_str_list(self.drama_genres),
+            all_genres: into_owned_str_list(self.all_genres),
+            duration: into_owned_str_list(self.duration),
+            kinopoisk_rating: into_owned_str_list(self.kinopoisk_rating),
+            imdb_rating: into_owned_str_list(self.imdb_rating),
+            shikimori_rating: into_owned_str_list(self.shikimori_rating),
+            mydramalist_rating: into_owned_str_list(self.mydramalist_rating),
+            actors: into_owned_str_list(self.actors),
+            directors: into_owned_str_list(self.directors),
+            producers: into_owned_str_list(self.producers),
+            writers: into_owned_str_list(self.writers),
+            composers: into_owned_str_list(self.composers),
+            editors: into_owned_str_list(self.editors),
+            designers: into_owned_str_list(self.designers),
+            operators: into_owned_str_list(self.operators),
+            rating_mpaa: into_owned_list(self.rating_mpaa),
+            minimal_age: into_owned_str_list(self.minimal_age),
+            anime_kind: into_owned_list(self.anime_kind),
+            mydramalist_tags: into_owned_str_list(self.mydramalist_tags),
+            anime_status: into_owned_list(self.anime_status),
+            drama_status: into_owned_list(self.drama_status),
+            all_status: into_owned_list(self.all_status),
+            anime_studios: into_owned_str_list(self.anime_studios),
+            anime_licensed_by: into_owned_str_list(self.anime_licensed_by),
+            accept_language: into_owned_str(self.accept_language),
+            client: self.client,
+        }
+    }
+
     /// Execute the query and fetch the results.
-    pub async fn execute<'b>(&'a self, client: &'b Client) -> Result<SearchResponse, Error> {
-        let payload = serialize_into_query_parts(self)?;
+    ///
+    /// # Errors
+    /// Without sending a request, returns [`Error::InvalidQuery`] for the combinations of parameters rejected by [`SearchQuery::validate`], and [`Error::InvalidParameter`] for a list value containing a comma
+    pub async fn execute(&self, client: &Client) -> Result<SearchResponse, Error> {
+        self.validate()?;
 
-        let response = client
-            .init_post_request("/search")
-            .query(&payload)
-            .send()
-            .await
-            .map_err(Error::HttpError)?;
+        let payload = self.query_parts()?;
 
-        let result = response
-            .json::<SearchResponseUnion>()
-            .await
-            .map_err(Error::HttpError)?;
+        let request_builder = client
+            .init_localized_post_request("/search", self.accept_language.as_deref())
+            .query(&payload);
+
+        let result = client
+            .execute_request::<SearchResponseUnion>(request_builder)
+            .await?;
 
         match result {
             SearchResponseUnion::Result(result) => Ok(result),
             SearchResponseUnion::Error { error } => Err(Error::KodikError(error)),
         }
     }
+
+    /// Execute the query with the client it was created by, see [`Client::search`]
+    ///
+    /// Fails with [`Error::InvalidQuery`] if the query was created with [`SearchQuery::new`], use [`SearchQuery::execute`] for it.
+    pub async fn send(&self) -> Result<SearchResponse, Error> {
+        let client = self.client.as_ref().ok_or_else(|| {
+            Error::InvalidQuery("the query is not bound to a client, use `execute`".to_owned())
+        })?;
+
+        self.execute(client).await
+    }
+
+    /// Same as `execute`, but also returns the status and the headers of the response
+    ///
+    /// Requests are not coalesced with [`crate::ClientBuilder::single_flight`], since every caller gets the headers of its own response.
+    ///
+    /// ```
+    /// use kodik_api::{search::SearchQuery, Client};
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let (response, meta) = SearchQuery::new()
+    ///     .with_title("Cyberpunk: Edgerunners")
+    ///     .execute_raw(&client)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("total = {}", response.total);
+    /// println!("retry after = {:?}", meta.headers.get("retry-after"));
+    /// # }
+    /// ```
+    pub async fn execute_raw(
+        &self,
+        client: &Client,
+    ) -> Result<(SearchResponse, ResponseMeta), Error> {
+        self.validate()?;
+
+        let payload = self.query_parts()?;
+
+        let request_builder = client
+            .init_localized_post_request("/search", self.accept_language.as_deref())
+            .query(&payload);
+
+        let (meta, result) = client
+            .send_request_with_headers::<SearchResponseUnion>(request_builder)
+            .await?;
+
+        match result {
+            SearchResponseUnion::Result(result) => Ok((result, meta)),
+            SearchResponseUnion::Error { error } => Err(Error::KodikError(error)),
+        }
+    }
+
+    /// Stream the query
+    ///
+    /// The query is validated like in `execute`. The first page is requested like `execute`, the following pages are requested from the `next_page` link. Parameters that are missing from the `next_page` link are re-sent with it, so that every page is fetched with the same parameters.
+    ///
+    /// ```
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use kodik_api::{search::SearchQuery, Client};
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let stream = SearchQuery::new()
+    ///     .with_title("Naruto")
+    ///     .with_limit(100)
+    ///     .stream(&client);
+    ///
+    /// pin_mut!(stream);
+    ///
+    /// while let Some(response) = stream.next().await {
+    ///     println!("response = {response:#?}");
+    /// }
+    /// # }
+    /// ```
+    pub fn stream(&self, client: &Client) -> impl Stream<Item = Result<SearchResponse, Error>> {
+        stream_pages_from::<SearchResponseUnion>(
+            client,
+            "/search",
+            self.validate().and_then(|()| self.query_parts()),
+            self.accept_language.as_deref().map(str::to_owned),
+            None,
+        )
+    }
+
+    /// Returns a pager over the results of the query with `page_size` results per page
+    ///
+    /// The query is validated like in `execute`, `page_size` replaces the `limit` of the query.
+    pub fn pager(&self, client: &Client, page_size: u32) -> Result<SearchPager, Error> {
+        let mut query = self.clone();
+
+        query.with_limit(page_size);
+        query.validate()?;
+
+        Ok(SearchPager {
+            client: client.clone(),
+            payload: query.query_parts()?,
+            accept_language: self.accept_language.as_deref().map(str::to_owned),
+            cursors: Vec::new(),
+            last_page: None,
+        })
+    }
+
+    /// Execute the query through the proxy registered with [`crate::ClientBuilder::labeled_proxy`]
+    ///
+    /// Same as `execute` with [`Client::via_proxy`]
+    pub async fn execute_via(
+        &self,
+        client: &Client,
+        proxy_label: &str,
+    ) -> Result<SearchResponse, Error> {
+        self.execute(&client.via_proxy(proxy_label)?).await
+    }
 }
 
 impl<'a> Default for SearchQuery<'a> {
@@ -771,3 +1586,1567 @@ impl<'a> Default for SearchQuery<'a> {
         Self::new()
     }
 }
+
+impl Client {
+    /// Create a [`SearchQuery`] bound to this client, so it can be executed with [`SearchQuery::send`]
+    ///
+    /// ```
+    /// use kodik_api::Client;
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let response = client
+    ///     .search()
+    ///     .with_title("Cyberpunk: Edgerunners")
+    ///     .send()
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("{response:#?}");
+    /// # }
+    /// ```
+    pub fn search<'a>(&self) -> SearchQuery<'a> {
+        SearchQuery {
+            client: Some(self.clone()),
+            ..SearchQuery::new()
+        }
+    }
+
+    /// Search several titles concurrently
+    ///
+    /// Runs at most `concurrency` searches at a time and yields every response tagged with the title it was requested for. Responses are yielded in completion order, not in the order of `titles`. New requests are only started while the stream is being polled.
+    ///
+    /// With [`FailureMode::Collect`] an error for one title does not affect the others. With [`FailureMode::FailFast`] the stream ends after the first error, dropping the requests that are still in flight.
+    ///
+    /// ```
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use kodik_api::{Client, FailureMode};
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let stream = client.search_titles(
+    ///     &["Cyberpunk: Edgerunners", "Chainsaw Man"],
+    ///     1,
+    ///     2,
+    ///     FailureMode::Collect,
+    /// );
+    ///
+    /// pin_mut!(stream);
+    ///
+    /// while let Some((title, response)) = stream.next().await {
+    ///     println!("{title} = {response:#?}");
+    /// }
+    /// # }
+    /// ```
+    pub fn search_titles<'a>(
+        &'a self,
+        titles: &'a [&'a str],
+        per_title_limit: u32,
+        concurrency: usize,
+        failure_mode: FailureMode,
+    ) -> impl Stream<Item = (String, Result<SearchResponse, Error>)> + 'a {
+        let responses = stream::iter(titles.iter().copied())
+            .map(move |title| async move {
+                let result = SearchQuery::new()
+                    .with_title(title)
+                    .with_limit(per_title_limit)
+                    .execute(self)
+                    .await;
+
+                (title.to_owned(), result)
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        fn_stream(move |emitter| async move {
+            pin_mut!(responses);
+
+            while let Some((title, result)) = responses.next().await {
+                let failed = result.is_err();
+
+                emitter.emit((title, result)).await;
+
+                if failed && failure_mode == FailureMode::FailFast {
+                    break;
+                }
+            }
+        })
+    }
+
+    /// Fetch the page of search results the cursor points to
+    ///
+    /// The page is requested from the link as returned by the API, so it is fetched with the parameters the API kept in it.
+    pub async fn fetch_page(&self, cursor: &PageCursor) -> Result<SearchResponse, Error> {
+        let result = self
+            .execute_request::<SearchResponseUnion>(self.init_post_request(cursor.as_str()))
+            .await?;
+
+        match result {
+            SearchResponseUnion::Result(result) => Ok(result),
+            SearchResponseUnion::Error { error } => Err(Error::KodikError(error)),
+        }
+    }
+
+    /// Fetch a single release by its Kodik ID, for example `serial-45534`
+    ///
+    /// Returns `None` if nothing is found. Every translation of a title is a separate release with its own ID, so normally there is at most one match. If the API still returns several rows, the first one is returned. With `with_material_data` the release includes the `material_data` field.
+    ///
+    /// ```
+    /// use kodik_api::Client;
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let release = client.get_release("serial-45534", true).await.unwrap();
+    ///
+    /// println!("release = {release:#?}");
+    /// # }
+    /// ```
+    pub async fn get_release(
+        &self,
+        id: &str,
+        with_material_data: bool,
+    ) -> Result<Option<Release>, Error> {
+        let mut query = SearchQuery::new();
+
+        query.with_id(id);
+
+        if with_material_data {
+            query.with_material_data(true);
+        }
+
+        let response = query.execute(self).await?;
+
+        Ok(response.results.into_iter().next())
+    }
+
+    /// Search several releases by their Kodik IDs concurrently
+    ///
+    /// Every ID is a separate request, and at most `concurrency` of them run at a time. Results are returned in the order of `ids`, each with the releases found for the ID or the error of its request. To fetch all the IDs with a single request use [`SearchQuery::with_ids`] instead.
+    ///
+    /// With [`FailureMode::Collect`] an error for one ID does not affect the others. With [`FailureMode::FailFast`] the results end with the first failed ID in the order of `ids`, dropping the requests that are still in flight.
+    ///
+    /// ```
+    /// use kodik_api::{Client, FailureMode};
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let results = client
+    ///     .search_by_ids(&["serial-45534", "movie-9876"], 4, FailureMode::Collect)
+    ///     .await;
+    ///
+    /// for (id, releases) in results {
+    ///     println!("{id} = {releases:#?}");
+    /// }
+    /// # }
+    /// ```
+    pub async fn search_by_ids(
+        &self,
+        ids: &[&str],
+        concurrency: usize,
+        failure_mode: FailureMode,
+    ) -> Vec<(String, Result<Vec<Release>, Error>)> {
+        let responses = stream::iter(ids.iter().copied())
+            .map(|id| async move {
+                let result = SearchQuery::new()
+                    .with_id(id)
+                    .execute(self)
+                    .await
+                    .map(|response| response.results);
+
+                (id.to_owned(), result)
+            })
+            .buffered(concurrency.max(1));
+
+        pin_mut!(responses);
+
+        let mut results = vec![];
+
+        while let Some((id, result)) = responses.next().await {
+            let failed = result.is_err();
+
+            results.push((id, result));
+
+            if failed && failure_mode == FailureMode::FailFast {
+                break;
+            }
+        }
+
+        results
+    }
+
+    /// Stream the seasons of a series one by one
+    ///
+    /// Each season is fetched with a separate request with `season` and `with_episodes_data`, starting with the first one. The stream ends on the first season that doesn't exist, so seasons after a gap and the season `0` are never fetched. The stream also ends after the first error.
+    ///
+    /// ```
+    /// use futures_util::{pin_mut, StreamExt};
+    /// use kodik_api::Client;
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let stream = client.stream_seasons("serial-45534");
+    ///
+    /// pin_mut!(stream);
+    ///
+    /// while let Some(season) = stream.next().await {
+    ///     println!("season = {season:#?}");
+    /// }
+    /// # }
+    /// ```
+    pub fn stream_seasons<'a>(
+        &'a self,
+        id: &'a str,
+    ) -> impl Stream<Item = Result<UnifiedSeason, Error>> + 'a {
+        try_fn_stream(move |emitter| async move {
+            for season in 1.. {
+                let seasons = [season];
+
+                let response = SearchQuery::new()
+                    .with_id(id)
+                    .with_season(&seasons)
+                    .with_episodes_data(true)
+                    .execute(self)
+                    .await?;
+
+                let unified_season = response
+                    .results
+                    .first()
+                    .filter(|release| release.seasons.is_some())
+                    .and_then(|release| unify_seasons(release).remove(&NumericKey::from(season)));
+
+                let Some(unified_season) = unified_season else {
+                    break;
+                };
+
+                emitter.emit(unified_season).await;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures_util::future::BoxFuture;
+    use reqwest::{header::HeaderMap, Request, StatusCode};
+
+    use futures_util::TryStreamExt;
+    use serde_json::json;
+    use wiremock::{
+        matchers::{method, path, query_param, query_param_is_missing},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use crate::{
+        list::ListQuery,
+        transport::{MockTransport, Transport, TransportResponse},
+        types::{tests::get_default_kodik_release, EpisodeUnion, Season},
+        ClientBuilder, RetryPolicy,
+    };
+
+    use super::*;
+
+    fn get_search_response(translation_ids: &[i32]) -> SearchResponse {
+        SearchResponse {
+            time: "3ms".to_owned(),
+            total: translation_ids.len() as i32,
+            prev_page: None,
+            next_page: None,
+            results: translation_ids
+                .iter()
+                .enumerate()
+                .map(|(index, translation_id)| {
+                    let mut release = get_default_kodik_release();
+
+                    release.id = format!("serial-{index}");
+                    release.translation.id = *translation_id;
+
+                    release
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_response_round_trip() {
+        use crate::types::{
+            BlockedSeason, BlockedSeasons, Episode, EpisodeUnion, MaterialData, ReleaseQuality,
+            Season,
+        };
+
+        let link = "//kodik.info/seria/1049812/a3f6fc2d1a3b/720p";
+
+        let mut release = get_default_kodik_release();
+
+        release.quality = ReleaseQuality::Other("HDTVRip 4K".to_owned());
+        release.blocked_seasons = BlockedSeasons::PerSeason(BTreeMap::from([
+            ("1".to_owned(), BlockedSeason::All),
+            (
+                "2".to_owned(),
+                BlockedSeason::Episodes(vec!["3".to_owned()]),
+            ),
+        ]));
+        release.seasons = Some(BTreeMap::from([(
+            1.into(),
+            Season {
+                title: None,
+                link: "//kodik.info/season/45534/d8619e900d122ea8eff8b55891b09bac/720p".to_owned(),
+                episodes: BTreeMap::from([
+                    (1.into(), EpisodeUnion::Link(link.to_owned())),
+                    (
+                        2.into(),
+                        EpisodeUnion::Episode(Episode {
+                            title: Some("Спешл".to_owned()),
+                            link: link.to_owned(),
+                            screenshots: vec![],
+                        }),
+                    ),
+                ]),
+            },
+        )]));
+        release.material_data = Some(MaterialData {
+            anime_kind: Some(AnimeKind::Other("tv_special".to_owned())),
+            kinopoisk_rating: Some(8.1),
+            extra: HashMap::from([("rating_mpaa_new".to_owned(), json!("18+"))]),
+            ..MaterialData::default()
+        });
+
+        let response = SearchResponse {
+            time: "3ms".to_owned(),
+            total: 2,
+            prev_page: None,
+            next_page: Some("https://kodikapi.com/search?page=2".to_owned()),
+            results: vec![release, get_default_kodik_release()],
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+
+        let round_tripped: SearchResponse = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(round_tripped.time, response.time);
+        assert_eq!(round_tripped.total, response.total);
+        assert_eq!(round_tripped.prev_page, None);
+        assert_eq!(round_tripped.next_page, response.next_page);
+        assert_eq!(round_tripped.results, response.results);
+    }
+
+    #[tokio::test]
+    async fn test_empty_response() {
+        let transport = MockTransport::new();
+
+        transport.respond(
+            "/search",
+            200,
+            r#"{"time":"3ms","total":0,"prev_page":null,"next_page":null,"results":[]}"#,
+        );
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport.clone())
+            .build();
+
+        let response = SearchQuery::new().execute(&client).await.unwrap();
+
+        assert_eq!(response.total, 0);
+        assert!(response.results.is_empty());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_translation_priority() {
+        let mut search_response = get_search_response(&[610, 1978, 609, 610]);
+
+        assert!(!search_response.verify_translation_priority(&[609, 610]));
+        assert!(search_response.verify_translation_priority(&[]));
+
+        search_response.reorder_by_priority(&[609, 610]);
+
+        assert!(search_response.verify_translation_priority(&[609, 610]));
+        assert_eq!(
+            search_response
+                .results
+                .iter()
+                .map(|release| release.id.as_str())
+                .collect::<Vec<_>>(),
+            ["serial-2", "serial-0", "serial-3", "serial-1"]
+        );
+    }
+
+    #[test]
+    fn test_exclude_ids() {
+        let mut response = get_search_response(&[610, 609, 767]);
+
+        response.exclude_ids(&["serial-0", "serial-2", "serial-45534"]);
+
+        assert_eq!(
+            response
+                .results
+                .iter()
+                .map(|release| release.id.as_str())
+                .collect::<Vec<_>>(),
+            ["serial-1"]
+        );
+        assert_eq!(response.total, 3);
+    }
+
+    #[test]
+    fn test_best_title_match() {
+        let mut response = get_search_response(&[610, 609, 767]);
+
+        response.results[0].title = "Наруто".to_owned();
+        response.results[0].title_orig = "Naruto".to_owned();
+        response.results[0].other_title = None;
+        response.results[1].title = "Cyberpunk".to_owned();
+        response.results[1].other_title = Some("Edgerunners / Cyberpunk Edgerunners".to_owned());
+        response.results[2].title_orig = "Cyberpunk 2077".to_owned();
+
+        let best_match = |query| response.best_title_match(query).unwrap().id.as_str();
+
+        assert_eq!(best_match("НАРУТО!"), "serial-0");
+        assert_eq!(best_match("naruto"), "serial-0");
+        assert_eq!(best_match("cyberpunk: edgerunners"), "serial-1");
+        assert_eq!(best_match("Edgerunners"), "serial-1");
+        assert_eq!(best_match("Kiberpank begushchie po krayu"), "serial-2");
+
+        assert!(get_search_response(&[])
+            .best_title_match("Naruto")
+            .is_none());
+
+        assert_eq!(title_similarity("", ""), 1.0);
+        assert_eq!(title_similarity("Naruto", ""), 0.0);
+        assert_eq!(title_similarity("Naruto", "Naruta"), 1.0 - 1.0 / 6.0);
+    }
+
+    #[test]
+    fn test_prioritize_translations() {
+        let mut query = SearchQuery::new();
+
+        query
+            .with_prioritize_translations(&[
+                TranslationPriority::Id(610),
+                TranslationPriority::Voice,
+                TranslationPriority::Id(609),
+            ])
+            .with_unprioritize_translations(&[
+                TranslationPriority::Disable,
+                TranslationPriority::Subtitles,
+            ]);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [
+                (
+                    "prioritize_translations".to_owned(),
+                    "610,voice,609".to_owned()
+                ),
+                (
+                    "unprioritize_translations".to_owned(),
+                    "0,subtitles".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_not_blocked_in() {
+        let mut query = SearchQuery::new();
+
+        query
+            .with_title("Cyberpunk: Edgerunners")
+            .with_not_blocked_in(vec![CountryCode::new("KZ").unwrap()]);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [
+                ("title".to_owned(), "Cyberpunk: Edgerunners".to_owned()),
+                ("not_blocked_in".to_owned(), "KZ".to_owned()),
+            ]
+        );
+
+        query.with_not_blocked_for_me(true);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap()[2],
+            ("not_blocked_for_me".to_owned(), "true".to_owned())
+        );
+
+        query.with_not_blocked_for_me(false);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap()[2],
+            ("not_blocked_for_me".to_owned(), "false".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_with_ids() {
+        let mut query = SearchQuery::new();
+
+        query.with_ids(&["serial-1", "serial-2", "movie-3"]);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [("id".to_owned(), "serial-1,serial-2,movie-3".to_owned())]
+        );
+
+        // The last setter wins
+        query.with_id("serial-4");
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [("id".to_owned(), "serial-4".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_flags_match_list_query() {
+        let mut search_query = SearchQuery::new();
+
+        search_query
+            .with_seasons(true)
+            .with_episodes(true)
+            .with_episodes_data(true)
+            .with_page_links(true)
+            .with_material_data(true);
+
+        let mut list_query = ListQuery::new();
+
+        list_query
+            .with_seasons(true)
+            .with_episodes(true)
+            .with_episodes_data(true)
+            .with_page_links(true)
+            .with_material_data(true);
+
+        assert_eq!(
+            serialize_into_query_parts(&search_query).unwrap(),
+            serialize_into_query_parts(&list_query).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hosted_page_params() {
+        let params = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .with_page_links(false)
+            .hosted_page_params()
+            .unwrap();
+
+        assert_eq!(
+            params,
+            [
+                ("title".to_owned(), "Cyberpunk: Edgerunners".to_owned()),
+                ("with_page_links".to_owned(), "true".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_screenshots() {
+        let mut query = SearchQuery::new();
+
+        query
+            .with_title("Cyberpunk: Edgerunners")
+            .with_screenshots(false)
+            .with_episode_screenshots(false);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            [
+                ("title".to_owned(), "Cyberpunk: Edgerunners".to_owned()),
+                ("with_screenshots".to_owned(), "false".to_owned()),
+                ("with_episode_screenshots".to_owned(), "false".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_auto_material_data() {
+        let has_material_data = |query: &SearchQuery| {
+            query
+                .query_parts()
+                .unwrap()
+                .into_iter()
+                .find(|(key, _)| key == "with_material_data")
+                .map(|(_, value)| value)
+        };
+
+        assert_eq!(has_material_data(&SearchQuery::new()), None);
+        assert_eq!(
+            has_material_data(SearchQuery::new().with_genres(&["аниме"])),
+            Some("true".to_owned())
+        );
+        assert_eq!(
+            has_material_data(SearchQuery::new().with_kinopoisk_rating_range(Range::AtLeast(7.0))),
+            Some("true".to_owned())
+        );
+        assert_eq!(
+            has_material_data(
+                SearchQuery::new()
+                    .with_actors(&["Дзюнъити Сувабэ"])
+                    .with_material_data(false)
+            ),
+            Some("false".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_punctuated_title() {
+        let title = "Кровь, пот & слёзы: часть 2?";
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(query_param("title", title))
+            .and(query_param("genres", "боевые искусства"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "time": "3ms",
+                "total": 0,
+                "results": [],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let mut query = SearchQuery::new();
+
+        query
+            .with_title(title)
+            .with_genres(&["боевые искусства"])
+            .with_material_data(false);
+
+        assert_eq!(query.execute(&client).await.unwrap().total, 0);
+
+        // Search and list queries share the serialization of the parameters
+        let mut list_query = ListQuery::new();
+
+        list_query
+            .with_genres(&["боевые искусства"])
+            .with_material_data(false);
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap()[1..],
+            serialize_into_query_parts(&list_query).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_strict_without_title() {
+        assert!(matches!(
+            SearchQuery::new().with_strict(true).validate(),
+            Err(Error::InvalidQuery(_))
+        ));
+        assert!(matches!(
+            SearchQuery::new().with_full_match(true).validate(),
+            Err(Error::InvalidQuery(_))
+        ));
+
+        assert!(matches!(
+            SearchQuery::new()
+                .with_title(" ")
+                .with_strict(true)
+                .validate(),
+            Err(Error::InvalidQuery(_))
+        ));
+
+        assert!(SearchQuery::new().with_strict(false).validate().is_ok());
+        assert!(SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .with_strict(true)
+            .with_full_match(true)
+            .validate()
+            .is_ok());
+        assert!(SearchQuery::new()
+            .with_title_orig("Cyberpunk: Edgerunners")
+            .with_strict(true)
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_zero_limit() {
+        assert!(matches!(
+            SearchQuery::new().with_limit(0).validate(),
+            Err(Error::InvalidQuery(_))
+        ));
+        assert!(SearchQuery::new().with_limit(1).validate().is_ok());
+    }
+
+    #[test]
+    fn test_empty_ids() {
+        assert!(matches!(
+            SearchQuery::new()
+                .with_ids(Vec::<Cow<str>>::new())
+                .validate(),
+            Err(Error::InvalidQuery(_))
+        ));
+        assert!(SearchQuery::new()
+            .with_ids(&["serial-45534"])
+            .validate()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_execute_strict_without_title() {
+        let client = Client::new("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7");
+
+        let result = SearchQuery::new().with_strict(true).execute(&client).await;
+
+        assert!(matches!(result, Err(Error::InvalidQuery(_))));
+    }
+
+    #[tokio::test]
+    async fn test_episode_without_season() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(query_param("season", "1"))
+            .and(query_param("episode", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "time": "3ms",
+                "total": 0,
+                "results": [],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let mut query = SearchQuery::new();
+
+        query.with_id("serial-45534").with_episode(&[2]);
+
+        let result = query.execute(&client).await;
+
+        assert!(matches!(result, Err(Error::InvalidQuery(_))));
+
+        let stream = query.stream(&client);
+
+        pin_mut!(stream);
+
+        let result = stream.next().await.unwrap();
+
+        assert!(matches!(result, Err(Error::InvalidQuery(_))));
+
+        query.with_season(&[1]);
+
+        assert_eq!(query.execute(&client).await.unwrap().total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_seasons() {
+        let mock_server = MockServer::start().await;
+
+        for season in 1..=3 {
+            let mut release = get_default_kodik_release();
+
+            release.seasons = (season < 3).then(|| {
+                BTreeMap::from([(
+                    season.to_string().into(),
+                    Season {
+                        title: None,
+                        link: format!(
+                            "//kodik.info/season/45534/d8619e900d12/720p?season={season}"
+                        ),
+                        episodes: BTreeMap::from([(
+                            "1".into(),
+                            EpisodeUnion::Link(format!(
+                                "//kodik.info/seria/1049812/a3f6fc2d1a3b/720p?season={season}"
+                            )),
+                        )]),
+                    },
+                )])
+            });
+
+            let results = if season < 3 { vec![release] } else { vec![] };
+
+            Mock::given(method("POST"))
+                .and(path("/search"))
+                .and(query_param("id", "serial-45534"))
+                .and(query_param("season", season.to_string()))
+                .and(query_param("with_episodes_data", "true"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "time": "3ms",
+                    "total": results.len(),
+                    "results": results,
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let seasons: Vec<UnifiedSeason> = client
+            .stream_seasons("serial-45534")
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            seasons
+                .iter()
+                .map(|season| season.link.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "//kodik.info/season/45534/d8619e900d12/720p?season=1",
+                "//kodik.info/season/45534/d8619e900d12/720p?season=2",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream() {
+        let mock_server = MockServer::start().await;
+
+        let get_page = |id: &str, next_page: Option<String>| {
+            let mut release = get_default_kodik_release();
+
+            release.id = id.to_owned();
+
+            json!({
+                "time": "3ms",
+                "total": 2,
+                "prev_page": null,
+                "next_page": next_page,
+                "results": [release],
+            })
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(query_param_is_missing("page"))
+            .and(query_param("title", "Naruto"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_page(
+                "serial-1",
+                Some(format!("{}/search?page=2&limit=1", mock_server.uri())),
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(query_param("page", "2"))
+            .and(query_param("limit", "1"))
+            .and(query_param("title", "Naruto"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_page("serial-2", None)))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let pages: Vec<SearchResponse> = SearchQuery::new()
+            .with_title("Naruto")
+            .with_limit(1)
+            .stream(&client)
+            .try_collect()
+            .await
+            .unwrap();
+
+        let ids = pages
+            .iter()
+            .flat_map(|page| page.results.iter().map(|release| release.id.as_str()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, ["serial-1", "serial-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_titles_failure_mode() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(query_param("title", "Broken"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "error": "Отсутствует или неверный токен" })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "time": "3ms",
+                "total": 0,
+                "results": [],
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let titles = ["Naruto", "Broken", "Bleach"];
+
+        let responses = client
+            .search_titles(&titles, 1, 1, FailureMode::Collect)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            responses
+                .iter()
+                .map(|(title, result)| (title.as_str(), result.is_ok()))
+                .collect::<Vec<_>>(),
+            [("Naruto", true), ("Broken", false), ("Bleach", true)]
+        );
+
+        let responses = client
+            .search_titles(&titles, 1, 1, FailureMode::FailFast)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            responses
+                .iter()
+                .map(|(title, result)| (title.as_str(), result.is_ok()))
+                .collect::<Vec<_>>(),
+            [("Naruto", true), ("Broken", false)]
+        );
+    }
+
+    /// Responds to every search with a release whose ID is taken from the title, counting the requests in flight
+    #[derive(Default)]
+    struct TitleTransport {
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl Transport for TitleTransport {
+        fn send(&self, request: Request) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+            Box::pin(async move {
+                let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+
+                self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+
+                futures_timer::Delay::new(Duration::from_millis(20)).await;
+
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                let title = request
+                    .url()
+                    .query_pairs()
+                    .find(|(key, _)| key == "title")
+                    .map(|(_, title)| title.into_owned())
+                    .unwrap_or_default();
+
+                let mut release = get_default_kodik_release();
+
+                release.id = format!("id-{title}");
+
+                Ok(TransportResponse {
+                    status: StatusCode::OK,
+                    headers: HeaderMap::new(),
+                    body: json!({ "time": "3ms", "total": 1, "results": [release] }).to_string(),
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_titles() {
+        let transport = TitleTransport::default();
+        let max_in_flight = transport.max_in_flight.clone();
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport)
+            .build();
+
+        let titles = ["Naruto", "Bleach", "Chainsaw Man", "Frieren", "Monster"];
+
+        let mut responses = client
+            .search_titles(&titles, 1, 2, FailureMode::Collect)
+            .map(|(title, result)| (title, result.unwrap().results[0].id.clone()))
+            .collect::<Vec<_>>()
+            .await;
+
+        responses.sort();
+
+        // Every response is tagged with the title it was requested for
+        assert_eq!(
+            responses,
+            [
+                ("Bleach".to_owned(), "id-Bleach".to_owned()),
+                ("Chainsaw Man".to_owned(), "id-Chainsaw Man".to_owned()),
+                ("Frieren".to_owned(), "id-Frieren".to_owned()),
+                ("Monster".to_owned(), "id-Monster".to_owned()),
+                ("Naruto".to_owned(), "id-Naruto".to_owned()),
+            ]
+        );
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mock_transport() {
+        let transport = MockTransport::new();
+
+        transport.respond(
+            "/search",
+            200,
+            json!({
+                "time": "3ms",
+                "total": 1,
+                "prev_page": null,
+                "next_page": null,
+                "results": [get_default_kodik_release()],
+            })
+            .to_string(),
+        );
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport.clone())
+            .build();
+
+        let response = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .execute(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(response.results[0].id, "serial-45534");
+
+        let requests = transport.requests();
+
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0]
+            .query_pairs()
+            .any(|(key, value)| key == "title" && value == "Cyberpunk: Edgerunners"));
+    }
+
+    #[test]
+    fn test_async_std() {
+        let transport = MockTransport::new();
+
+        transport.respond(
+            "/search",
+            200,
+            json!({
+                "time": "3ms",
+                "total": 1,
+                "prev_page": null,
+                "next_page": null,
+                "results": [get_default_kodik_release()],
+            })
+            .to_string(),
+        );
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport)
+            .build();
+
+        // No Tokio runtime is running here
+        let response = async_std::task::block_on(
+            SearchQuery::new()
+                .with_title("Cyberpunk: Edgerunners")
+                .execute(&client),
+        )
+        .unwrap();
+
+        assert_eq!(response.results[0].id, "serial-45534");
+    }
+
+    #[test]
+    fn test_async_std_retry_and_rate_limit() {
+        let transport = MockTransport::new();
+
+        transport.respond("/search", 503, "Service Unavailable");
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport.clone())
+            .retry(RetryPolicy::new(2, Duration::from_millis(10)))
+            .rate_limit(1, Duration::from_millis(50))
+            .build();
+
+        let mut query = SearchQuery::new();
+
+        query.with_title("Cyberpunk: Edgerunners");
+
+        // No Tokio runtime is running here, the retry and the rate limit wait on their own timer
+        let result = async_std::task::block_on(query.execute(&client));
+
+        assert!(matches!(result, Err(Error::HttpStatus { status: 503, .. })));
+        assert_eq!(transport.requests().len(), 2);
+
+        transport.respond(
+            "/search",
+            200,
+            r#"{"time":"3ms","total":0,"prev_page":null,"next_page":null,"results":[]}"#,
+        );
+
+        let response = async_std::task::block_on(query.execute(&client)).unwrap();
+
+        assert_eq!(response.total, 0);
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_bound_query() {
+        let transport = MockTransport::new();
+
+        transport.respond(
+            "/search",
+            200,
+            r#"{"time":"3ms","total":0,"prev_page":null,"next_page":null,"results":[]}"#,
+        );
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .transport(transport.clone())
+            .build();
+
+        let title = "Cyberpunk: Edgerunners".to_owned();
+
+        let response = client.search().with_title(title.as_str()).send().await;
+
+        assert_eq!(response.unwrap().total, 0);
+        assert_eq!(transport.requests().len(), 1);
+
+        let result = SearchQuery::new().with_title(title.as_str()).send().await;
+
+        assert!(matches!(result, Err(Error::InvalidQuery(_))));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pager() {
+        let mock_server = MockServer::start().await;
+
+        let get_page = |page: u32, next_page: Option<u32>| {
+            let mut release = get_default_kodik_release();
+
+            release.id = format!("serial-{page}");
+
+            json!({
+                "time": "3ms",
+                "total": 3,
+                "prev_page": null,
+                "next_page": next_page.map(|page| format!("{}/search?page={page}", mock_server.uri())),
+                "results": [release],
+            })
+        };
+
+        for (page, next_page) in [(1, Some(2)), (2, Some(3)), (3, None)] {
+            let mock = Mock::given(method("POST"))
+                .and(path("/search"))
+                .and(query_param("title", "Naruto"))
+                .and(query_param("limit", "1"));
+
+            let mock = if page == 1 {
+                mock.and(query_param_is_missing("page"))
+            } else {
+                mock.and(query_param("page", page.to_string()))
+            };
+
+            mock.respond_with(ResponseTemplate::new(200).set_body_json(get_page(page, next_page)))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let mut pager = SearchQuery::new()
+            .with_title("Naruto")
+            .pager(&client, 1)
+            .unwrap();
+
+        let page = pager.page(2).await.unwrap().unwrap();
+
+        assert_eq!(page.results[0].id, "serial-3");
+        assert_eq!(pager.visited_pages(), 3);
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+
+        // Walks from the cached cursor of the page
+        let page = pager.page(1).await.unwrap().unwrap();
+
+        assert_eq!(page.results[0].id, "serial-2");
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 4);
+
+        // The last page is known, so no more requests are sent
+        assert!(pager.page(3).await.unwrap().is_none());
+        assert!(pager.page(10).await.unwrap().is_none());
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 4);
+
+        let mut pager = SearchQuery::new()
+            .with_title("Naruto")
+            .pager(&client, 1)
+            .unwrap();
+
+        assert!(pager.page(5).await.unwrap().is_none());
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page() {
+        let mock_server = MockServer::start().await;
+
+        let get_page = |id: &str, prev_page: Option<u32>, next_page: Option<u32>| {
+            let mut release = get_default_kodik_release();
+
+            release.id = id.to_owned();
+
+            let page_url = |page: Option<u32>| {
+                page.map(|page| format!("{}/search?title=Naruto&page={page}", mock_server.uri()))
+            };
+
+            json!({
+                "time": "3ms",
+                "total": 2,
+                "prev_page": page_url(prev_page),
+                "next_page": page_url(next_page),
+                "results": [release],
+            })
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(query_param_is_missing("page"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_page(
+                "serial-1",
+                None,
+                Some(2),
+            )))
+            .mount(&mock_server)
+            .await;
+
+        for (page, id, prev_page, next_page) in [
+            (1, "serial-1", None, Some(2)),
+            (2, "serial-2", Some(1), None),
+        ] {
+            Mock::given(method("POST"))
+                .and(path("/search"))
+                .and(query_param("page", page.to_string()))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(get_page(id, prev_page, next_page)),
+                )
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let first_page = SearchQuery::new()
+            .with_title("Naruto")
+            .execute(&client)
+            .await
+            .unwrap();
+
+        assert!(first_page.prev_cursor().is_none());
+
+        let second_page = client
+            .fetch_page(&first_page.next_cursor().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(second_page.results[0].id, "serial-2");
+        assert!(second_page.next_cursor().is_none());
+
+        let back_page = client
+            .fetch_page(&second_page.prev_cursor().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(back_page.results[0].id, first_page.results[0].id);
+    }
+
+    #[test]
+    fn test_page_cursor_debug() {
+        let cursor = PageCursor::from(
+            "https://kodikapi.com/search?token=q8p5vnf9crt7xfyzke4iwc6r5rvsurv7&page=2",
+        );
+
+        assert_eq!(
+            format!("{cursor:?}"),
+            r#"PageCursor("https://kodikapi.com/search?token=***&page=2")"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_raw() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Retry-After", "120")
+                    .set_body_json(json!({
+                        "time": "3ms",
+                        "total": 0,
+                        "results": [],
+                    })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let (response, meta) = SearchQuery::new()
+            .with_title("Cyberpunk: Edgerunners")
+            .execute_raw(&client)
+            .await
+            .unwrap();
+
+        assert_eq!(response.total, 0);
+        assert_eq!(meta.status, 200);
+        assert_eq!(meta.headers.get("retry-after").unwrap(), "120");
+    }
+
+    #[tokio::test]
+    async fn test_get_release() {
+        let mock_server = MockServer::start().await;
+
+        let get_release = |id: &str, title: &str| {
+            let mut release = get_default_kodik_release();
+
+            release.id = id.to_owned();
+            release.title = title.to_owned();
+
+            release
+        };
+
+        for (id, results) in [
+            ("serial-1", vec![get_release("serial-1", "First")]),
+            ("serial-2", vec![]),
+            (
+                "serial-3",
+                vec![
+                    get_release("serial-3", "First"),
+                    get_release("serial-3", "Second"),
+                ],
+            ),
+        ] {
+            Mock::given(method("POST"))
+                .and(path("/search"))
+                .and(query_param("id", id))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "time": "3ms",
+                    "total": results.len(),
+                    "results": results,
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let release = client.get_release("serial-1", true).await.unwrap().unwrap();
+
+        assert_eq!(release.id, "serial-1");
+
+        assert!(client
+            .get_release("serial-2", false)
+            .await
+            .unwrap()
+            .is_none());
+
+        let release = client
+            .get_release("serial-3", false)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(release.title, "First");
+
+        let requests = mock_server.received_requests().await.unwrap();
+
+        assert!(requests[0]
+            .url
+            .query_pairs()
+            .any(|(key, value)| key == "with_material_data" && value == "true"));
+        assert!(!requests[1]
+            .url
+            .query_pairs()
+            .any(|(key, _)| key == "with_material_data"));
+    }
+
+    #[tokio::test]
+    async fn test_search_by_ids() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(query_param("id", "serial-2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "error": "Отсутствует или неверный токен" })),
+            )
+            .mount(&mock_server)
+            .await;
+
+        for (id, delay) in [("serial-1", 100), ("serial-3", 0)] {
+            let mut release = get_default_kodik_release();
+
+            release.id = id.to_owned();
+
+            Mock::given(method("POST"))
+                .and(path("/search"))
+                .and(query_param("id", id))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(json!({
+                            "time": "3ms",
+                            "total": 1,
+                            "results": [release],
+                        }))
+                        .set_delay(Duration::from_millis(delay)),
+                )
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let results = client
+            .search_by_ids(
+                &["serial-1", "serial-2", "serial-3"],
+                3,
+                FailureMode::Collect,
+            )
+            .await;
+
+        // The slowest request is still the first one
+        assert_eq!(
+            results
+                .iter()
+                .map(|(id, result)| (
+                    id.as_str(),
+                    result.as_ref().ok().map(|releases| releases
+                        .iter()
+                        .map(|release| release.id.as_str())
+                        .collect::<Vec<_>>())
+                ))
+                .collect::<Vec<_>>(),
+            [
+                ("serial-1", Some(vec!["serial-1"])),
+                ("serial-2", None),
+                ("serial-3", Some(vec!["serial-3"])),
+            ]
+        );
+
+        let results = client
+            .search_by_ids(
+                &["serial-1", "serial-2", "serial-3"],
+                3,
+                FailureMode::FailFast,
+            )
+            .await;
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|(id, result)| (id.as_str(), result.is_ok()))
+                .collect::<Vec<_>>(),
+            [("serial-1", true), ("serial-2", false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_owned_query() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .and(query_param("title", "Cyberpunk: Edgerunners"))
+            .and(query_param("types", "anime-serial"))
+            .and(query_param("countries", "Япония"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "time": "3ms",
+                "total": 0,
+                "results": [],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let title = "Cyberpunk: Edgerunners".to_owned();
+        let countries = vec!["Япония".to_owned()];
+
+        let query = SearchQuery::new()
+            .with_title(title.as_str())
+            .with_types(&[ReleaseType::AnimeSerial])
+            .with_countries(&countries)
+            .clone()
+            .into_owned();
+
+        let owned_query = SearchQuery::new()
+            .with_title(title.clone())
+            .with_types(vec![ReleaseType::AnimeSerial])
+            .with_countries(countries.clone())
+            .clone();
+
+        drop((title, countries));
+
+        assert_eq!(
+            serialize_into_query_parts(&query).unwrap(),
+            serialize_into_query_parts(&owned_query).unwrap()
+        );
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let response = tokio::spawn(async move { query.execute(&client).await })
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(response.total, 0);
+    }
+}
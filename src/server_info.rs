@@ -0,0 +1,124 @@
+use reqwest::header::{HeaderMap, SERVER};
+use serde::Deserialize;
+
+use crate::{error::Error, types::ProcessingTime, util::ResponseUnion, Client};
+
+/// Information about the API server, taken from a lightweight request
+///
+/// Kodik doesn't have a version or capabilities endpoint, so there is no API version to report. The server and the headers of the response are the closest thing to it.
+#[derive(Debug, Clone)]
+pub struct ServerInfo {
+    /// Time the server took to process the request, as reported by the API, for example `3ms`
+    pub time: String,
+
+    /// The `Server` header of the response
+    pub server: Option<String>,
+
+    /// All headers of the response
+    pub headers: HeaderMap,
+}
+
+impl ProcessingTime for ServerInfo {
+    fn time(&self) -> &str {
+        &self.time
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ServerInfoResponse {
+    time: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum ServerInfoResponseUnion {
+    Result(ServerInfoResponse),
+    Error { error: String },
+}
+
+impl ResponseUnion for ServerInfoResponseUnion {
+    fn is_error(&self) -> bool {
+        matches!(self, ServerInfoResponseUnion::Error { .. })
+    }
+}
+
+impl Client {
+    /// Returns information about the API server
+    ///
+    /// Sends a request to the small `/qualities/v2` endpoint, so it also checks that the API key is accepted.
+    ///
+    /// ```
+    /// use kodik_api::Client;
+    ///
+    /// # async fn run() {
+    /// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+    ///
+    /// let server_info = client.server_info().await.unwrap();
+    ///
+    /// println!("server = {:?}, time = {}", server_info.server, server_info.time);
+    /// # }
+    /// ```
+    pub async fn server_info(&self) -> Result<ServerInfo, Error> {
+        let (meta, result) = self
+            .send_request_with_headers::<ServerInfoResponseUnion>(
+                self.init_post_request("/qualities/v2"),
+            )
+            .await?;
+
+        match result {
+            ServerInfoResponseUnion::Result(result) => Ok(ServerInfo {
+                time: result.time,
+                server: meta
+                    .headers
+                    .get(SERVER)
+                    .and_then(|server| server.to_str().ok())
+                    .map(str::to_owned),
+                headers: meta.headers,
+            }),
+            ServerInfoResponseUnion::Error { error } => Err(Error::KodikError(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use crate::ClientBuilder;
+
+    #[tokio::test]
+    async fn test_server_info() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/qualities/v2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("server", "nginx")
+                    .set_body_json(json!({
+                        "time": "3ms",
+                        "total": 1,
+                        "prev_page": null,
+                        "next_page": null,
+                        "results": [{ "title": "WEB-DLRip 720p", "count": 5678 }],
+                    })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+            .api_url(mock_server.uri())
+            .build();
+
+        let server_info = client.server_info().await.unwrap();
+
+        assert_eq!(server_info.time, "3ms");
+        assert_eq!(server_info.server.as_deref(), Some("nginx"));
+    }
+}
@@ -0,0 +1,152 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+
+use crate::error::Error;
+
+type SharedResult = Result<Arc<dyn Any + Send + Sync>, Arc<Error>>;
+
+type InFlightRequests = HashMap<String, Shared<BoxFuture<'static, SharedResult>>>;
+
+/// Coalesces identical concurrent requests into a single one
+#[derive(Clone, Default)]
+pub(crate) struct SingleFlight {
+    in_flight: Arc<Mutex<InFlightRequests>>,
+}
+
+impl SingleFlight {
+    /// Runs `future` or joins the one that is already in flight with the same `key`
+    ///
+    /// The key must identify the result type too, otherwise joined callers would not be able to read the result.
+    pub(crate) async fn run<T, F>(&self, key: String, future: F) -> Result<T, Error>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<T, Error>> + Send + 'static,
+    {
+        let shared = {
+            let mut in_flight = self.in_flight.lock().expect("single flight lock poisoned");
+
+            let requests = self.in_flight.clone();
+
+            in_flight
+                .entry(key.clone())
+                .or_insert_with(|| {
+                    async move {
+                        let result = future.await;
+
+                        requests
+                            .lock()
+                            .expect("single flight lock poisoned")
+                            .remove(&key);
+
+                        result
+                            .map(|value| Arc::new(value) as Arc<dyn Any + Send + Sync>)
+                            .map_err(Arc::new)
+                    }
+                    .boxed()
+                    .shared()
+                })
+                .clone()
+        };
+
+        match shared.await {
+            Ok(value) => {
+                let value = value
+                    .downcast::<T>()
+                    .expect("single flight result type mismatch");
+
+                Ok(Arc::try_unwrap(value).unwrap_or_else(|value| (*value).clone()))
+            }
+            // The error is only shared if other callers are still holding it
+            Err(err) => Err(Arc::try_unwrap(err).unwrap_or_else(Error::SharedRequestError)),
+        }
+    }
+}
+
+impl fmt::Debug for SingleFlight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let in_flight = self.in_flight.lock().map(|in_flight| in_flight.len());
+
+        f.debug_struct("SingleFlight")
+            .field("in_flight", &in_flight.unwrap_or_default())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+        task::Poll,
+    };
+
+    use futures_util::future::{join, join_all, poll_fn};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_coalesce_concurrent_calls() {
+        let single_flight = SingleFlight::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let released = Arc::new(AtomicBool::new(false));
+
+        let requests = join_all((0..5).map(|_| {
+            let calls = calls.clone();
+            let released = released.clone();
+
+            single_flight.run("key".to_owned(), async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+
+                // Keep the request in flight until every caller has joined it
+                poll_fn(|cx| {
+                    if released.load(Ordering::SeqCst) {
+                        return Poll::Ready(());
+                    }
+
+                    cx.waker().wake_by_ref();
+
+                    Poll::Pending
+                })
+                .await;
+
+                Ok::<_, Error>("value".to_owned())
+            })
+        }));
+
+        let (results, _) = join(requests, async {
+            released.store(true, Ordering::SeqCst);
+        })
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(results.into_iter().all(|result| result.unwrap() == "value"));
+
+        single_flight
+            .run("key".to_owned(), async { Ok::<_, Error>(()) })
+            .await
+            .unwrap();
+
+        assert_eq!(single_flight.in_flight.lock().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_error_is_not_wrapped_for_single_caller() {
+        let single_flight = SingleFlight::default();
+
+        let result = single_flight
+            .run("key".to_owned(), async {
+                Err::<(), _>(Error::KodikError(
+                    "Отсутствует или неверный токен".to_owned(),
+                ))
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::KodikError(_))));
+    }
+}
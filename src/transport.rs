@@ -0,0 +1,150 @@
+use futures_util::future::BoxFuture;
+use reqwest::{header::HeaderMap, Client as ReqwestClient, Request, StatusCode};
+
+use crate::error::Error;
+
+/// A response received by a [`Transport`]
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+
+    pub headers: HeaderMap,
+
+    pub body: String,
+}
+
+/// Sends the requests of a [`crate::Client`]
+///
+/// The reqwest client is the default transport. A custom one can be set with [`crate::ClientBuilder::transport`], for example to test code built on this crate without the real API, see `MockTransport` behind the `test-util` feature.
+pub trait Transport: Send + Sync {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<TransportResponse, Error>>;
+}
+
+impl Transport for ReqwestClient {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            let response = self.execute(request).await.map_err(Error::HttpError)?;
+
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.text().await.map_err(Error::HttpError)?;
+
+            Ok(TransportResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+pub use mock::MockTransport;
+
+#[cfg(any(test, feature = "test-util"))]
+mod mock {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use futures_util::future::BoxFuture;
+    use reqwest::{header::HeaderMap, Request, StatusCode, Url};
+
+    use super::{Transport, TransportResponse};
+    use crate::error::Error;
+
+    /// A [`Transport`] which responds with canned bodies by the path of the request
+    ///
+    /// Requests to a path without a response fail with the `404` status. Clones share the responses and the sent requests.
+    ///
+    /// ```
+    /// use kodik_api::{search::SearchQuery, transport::MockTransport, ClientBuilder};
+    ///
+    /// # async fn run() {
+    /// let transport = MockTransport::new();
+    ///
+    /// transport.respond(
+    ///     "/search",
+    ///     200,
+    ///     r#"{"time":"3ms","total":0,"prev_page":null,"next_page":null,"results":[]}"#,
+    /// );
+    ///
+    /// let client = ClientBuilder::new()
+    ///     .api_key("q8p5vnf9crt7xfyzke4iwc6r5rvsurv7")
+    ///     .transport(transport.clone())
+    ///     .build();
+    ///
+    /// let response = SearchQuery::new()
+    ///     .with_title("Cyberpunk: Edgerunners")
+    ///     .execute(&client)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// assert_eq!(response.total, 0);
+    /// assert_eq!(transport.requests().len(), 1);
+    /// # }
+    /// ```
+    #[derive(Debug, Clone, Default)]
+    pub struct MockTransport {
+        responses: Arc<Mutex<HashMap<String, (StatusCode, String)>>>,
+        requests: Arc<Mutex<Vec<Url>>>,
+    }
+
+    impl MockTransport {
+        /// Constructs a new `MockTransport` without responses
+        pub fn new() -> MockTransport {
+            MockTransport::default()
+        }
+
+        /// Respond to the requests to `path` with the status and the body, replacing the previous response
+        ///
+        /// # Panic
+        /// If the status is not a valid HTTP status code
+        pub fn respond(&self, path: &str, status: u16, body: impl Into<String>) -> &MockTransport {
+            let status = StatusCode::from_u16(status).expect("invalid status code");
+
+            self.responses
+                .lock()
+                .expect("mock transport lock poisoned")
+                .insert(path.to_owned(), (status, body.into()));
+
+            self
+        }
+
+        /// Returns the URLs of the sent requests in the order they were sent
+        pub fn requests(&self) -> Vec<Url> {
+            self.requests
+                .lock()
+                .expect("mock transport lock poisoned")
+                .clone()
+        }
+    }
+
+    impl Transport for MockTransport {
+        fn send(&self, request: Request) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+            let url = request.url().clone();
+
+            let (status, body) = self
+                .responses
+                .lock()
+                .expect("mock transport lock poisoned")
+                .get(url.path())
+                .cloned()
+                .unwrap_or((StatusCode::NOT_FOUND, String::new()));
+
+            self.requests
+                .lock()
+                .expect("mock transport lock poisoned")
+                .push(url);
+
+            Box::pin(async move {
+                Ok(TransportResponse {
+                    status,
+                    headers: HeaderMap::new(),
+                    body,
+                })
+            })
+        }
+    }
+}
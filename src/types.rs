@@ -1,9 +1,26 @@
-use serde::{Deserialize, Serialize};
-
-use std::collections::BTreeMap;
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, Utc};
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    error::Error,
+    unify_seasons::{unify_seasons, EpisodeLink},
+};
 
 /// Represents a release type on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ReleaseType {
     #[serde(rename = "foreign-movie")]
     ForeignMovie,
@@ -29,10 +46,110 @@ pub enum ReleaseType {
     AnimeSerial,
     #[serde(rename = "multi-part-film")]
     MultiPartFilm,
+    /// A release type unknown to this version of the crate. It is dropped from the types of queries, since the API would not match it anyway
+    #[serde(other, rename = "unknown")]
+    Unknown,
+}
+
+impl ReleaseType {
+    /// Returns the category this release type belongs to, [`ReleaseType::Unknown`] has none
+    pub fn category(&self) -> Option<Category> {
+        match self {
+            ReleaseType::Anime | ReleaseType::AnimeSerial => Some(Category::Anime),
+            ReleaseType::ForeignMovie | ReleaseType::RussianMovie | ReleaseType::MultiPartFilm => {
+                Some(Category::Movies)
+            }
+            ReleaseType::ForeignSerial | ReleaseType::RussianSerial => Some(Category::Series),
+            ReleaseType::SovietCartoon
+            | ReleaseType::ForeignCartoon
+            | ReleaseType::RussianCartoon
+            | ReleaseType::CartoonSerial => Some(Category::Cartoons),
+            ReleaseType::DocumentarySerial => Some(Category::Documentaries),
+            ReleaseType::Unknown => None,
+        }
+    }
+
+    /// Drops [`ReleaseType::Unknown`] from the types of a query, the result is checked by [`ReleaseType::validate_query_types`]
+    pub(crate) fn known_only(types: Cow<'_, [ReleaseType]>) -> Cow<'_, [ReleaseType]> {
+        if types.contains(&ReleaseType::Unknown) {
+            types
+                .iter()
+                .filter(|release_type| **release_type != ReleaseType::Unknown)
+                .cloned()
+                .collect()
+        } else {
+            types
+        }
+    }
+
+    /// Rejects a types filter without known types, so that the query isn't silently sent without a filter
+    pub(crate) fn validate_query_types(types: Option<&[ReleaseType]>) -> Result<(), Error> {
+        if types.is_some_and(|types| types.is_empty()) {
+            return Err(Error::InvalidQuery(
+                "`types` must contain at least one known release type".to_owned(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents a group of release types as they are usually shown to users
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// [`ReleaseType::Anime`], [`ReleaseType::AnimeSerial`]
+    Anime,
+
+    /// [`ReleaseType::ForeignMovie`], [`ReleaseType::RussianMovie`], [`ReleaseType::MultiPartFilm`]
+    Movies,
+
+    /// [`ReleaseType::ForeignSerial`], [`ReleaseType::RussianSerial`]
+    Series,
+
+    /// [`ReleaseType::SovietCartoon`], [`ReleaseType::ForeignCartoon`], [`ReleaseType::RussianCartoon`], [`ReleaseType::CartoonSerial`]
+    Cartoons,
+
+    /// [`ReleaseType::DocumentarySerial`]
+    Documentaries,
+}
+
+impl Category {
+    /// Returns the release types of the category
+    pub fn release_types(&self) -> &'static [ReleaseType] {
+        match self {
+            Category::Anime => &[ReleaseType::Anime, ReleaseType::AnimeSerial],
+            Category::Movies => &[
+                ReleaseType::ForeignMovie,
+                ReleaseType::RussianMovie,
+                ReleaseType::MultiPartFilm,
+            ],
+            Category::Series => &[ReleaseType::ForeignSerial, ReleaseType::RussianSerial],
+            Category::Cartoons => &[
+                ReleaseType::SovietCartoon,
+                ReleaseType::ForeignCartoon,
+                ReleaseType::RussianCartoon,
+                ReleaseType::CartoonSerial,
+            ],
+            Category::Documentaries => &[ReleaseType::DocumentarySerial],
+        }
+    }
+
+    /// Returns the release types of all the categories without duplicates
+    pub fn release_types_of(categories: &[Category]) -> Vec<ReleaseType> {
+        let mut release_types: Vec<ReleaseType> = vec![];
+
+        for release_type in categories.iter().flat_map(Category::release_types) {
+            if !release_types.contains(release_type) {
+                release_types.push(release_type.clone());
+            }
+        }
+
+        release_types
+    }
 }
 
 /// Represents a release quality on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ReleaseQuality {
     #[serde(rename = "BDRip")]
     BdRip,
@@ -96,12 +213,112 @@ pub enum ReleaseQuality {
     WebDlRip720p,
     #[serde(rename = "Workprint-AVC")]
     WorkprintAvc,
-    #[serde(other)]
-    Unknown,
+    /// A quality this version of the crate doesn't know yet, with the raw value
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl ReleaseQuality {
+    /// Returns the value as it is sent and received by the API
+    pub fn as_wire_str(&self) -> &str {
+        match self {
+            ReleaseQuality::BdRip => "BDRip",
+            ReleaseQuality::BdRip1080p => "BDRip 1080p",
+            ReleaseQuality::BdRip720p => "BDRip 720p",
+            ReleaseQuality::CamRip => "CAMRip",
+            ReleaseQuality::DVhs => "D-VHS",
+            ReleaseQuality::DvbRip => "DVBRip",
+            ReleaseQuality::DvbRip720p => "DVBRip 720p",
+            ReleaseQuality::DvdRip => "DVDRip",
+            ReleaseQuality::DvdSrc => "DVDSrc",
+            ReleaseQuality::HddvdRip => "HDDVDRip",
+            ReleaseQuality::HddvdRip1080p => "HDDVDRip 1080p",
+            ReleaseQuality::HddvdRip720p => "HDDVDRip 720p",
+            ReleaseQuality::HdRip => "HDRip",
+            ReleaseQuality::HdRip1080p => "HDRip 1080p",
+            ReleaseQuality::HdRip720p => "HDRip 720p",
+            ReleaseQuality::HdtvRip => "HDTVRip",
+            ReleaseQuality::HdtvRip1080p => "HDTVRip 1080p",
+            ReleaseQuality::HdtvRip720p => "HDTVRip 720p",
+            ReleaseQuality::IptvRip => "IPTVRip",
+            ReleaseQuality::LaserdiscRip => "Laserdisc-RIP",
+            ReleaseQuality::SatRip => "SATRip",
+            ReleaseQuality::SuperTs => "SuperTS",
+            ReleaseQuality::Ts => "TS",
+            ReleaseQuality::Ts720p => "TS 720p",
+            ReleaseQuality::TvRip => "TVRip",
+            ReleaseQuality::TvRip720p => "TVRip 720p",
+            ReleaseQuality::VhsRip => "VHSRip",
+            ReleaseQuality::WebDlRip => "WEB-DLRip",
+            ReleaseQuality::WebDlRip1080p => "WEB-DLRip 1080p",
+            ReleaseQuality::WebDlRip720p => "WEB-DLRip 720p",
+            ReleaseQuality::WorkprintAvc => "Workprint-AVC",
+            ReleaseQuality::Other(value) => value,
+        }
+    }
+
+    /// Returns the rank of the quality by its resolution, the higher the better
+    ///
+    /// 1080p ranks above 720p, which ranks above HD rips without a marked resolution, then SD rips, then camera and telesync recordings. [`ReleaseQuality::Other`] ranks lowest, since nothing is known about it.
+    pub fn resolution_rank(&self) -> u16 {
+        match self {
+            ReleaseQuality::BdRip1080p
+            | ReleaseQuality::HddvdRip1080p
+            | ReleaseQuality::HdRip1080p
+            | ReleaseQuality::HdtvRip1080p
+            | ReleaseQuality::WebDlRip1080p => 1080,
+            ReleaseQuality::BdRip720p
+            | ReleaseQuality::DvbRip720p
+            | ReleaseQuality::HddvdRip720p
+            | ReleaseQuality::HdRip720p
+            | ReleaseQuality::HdtvRip720p
+            | ReleaseQuality::TvRip720p
+            | ReleaseQuality::WebDlRip720p => 720,
+            ReleaseQuality::BdRip
+            | ReleaseQuality::HddvdRip
+            | ReleaseQuality::HdRip
+            | ReleaseQuality::HdtvRip
+            | ReleaseQuality::WebDlRip => 576,
+            ReleaseQuality::DVhs
+            | ReleaseQuality::DvbRip
+            | ReleaseQuality::DvdRip
+            | ReleaseQuality::DvdSrc
+            | ReleaseQuality::IptvRip
+            | ReleaseQuality::LaserdiscRip
+            | ReleaseQuality::SatRip
+            | ReleaseQuality::TvRip
+            | ReleaseQuality::VhsRip => 480,
+            ReleaseQuality::Ts720p => 110,
+            ReleaseQuality::CamRip
+            | ReleaseQuality::SuperTs
+            | ReleaseQuality::Ts
+            | ReleaseQuality::WorkprintAvc => 100,
+            ReleaseQuality::Other(_) => 0,
+        }
+    }
+}
+
+/// Ordered by [`ReleaseQuality::resolution_rank`], qualities of the same rank are ordered by their wire value
+impl Ord for ReleaseQuality {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.resolution_rank()
+            .cmp(&other.resolution_rank())
+            .then_with(|| self.as_wire_str().cmp(other.as_wire_str()))
+    }
+}
+
+impl PartialOrd for ReleaseQuality {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 /// Represents a release on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// Only `PartialEq` and not `Eq`, since the ratings of [`MaterialData`] are floats.
+///
+/// The API has no parameter to select the fields of a release, so there is no partial variant of it. The response is trimmed by leaving out the optional parts instead: `with_material_data`, `with_seasons`, `with_episodes`, `with_screenshots` and the like of [`crate::search::SearchQuery`] and [`crate::list::ListQuery`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Release {
     /// `"movie-452654"`
     pub id: String,
@@ -122,18 +339,22 @@ pub struct Release {
     pub year: i32,
 
     /// `43949`
+    #[serde(default, deserialize_with = "deserialize_string_or_number")]
     pub kinopoisk_id: Option<String>,
 
     /// `tt0084716`
+    #[serde(default, deserialize_with = "deserialize_string_or_number")]
     pub imdb_id: Option<String>,
 
     /// `1245`
+    #[serde(default, deserialize_with = "deserialize_string_or_number")]
     pub mdl_id: Option<String>,
 
     /// Link to the material on World Art (not using ID because there are different
     pub worldart_link: Option<String>,
 
     /// `1234`
+    #[serde(default, deserialize_with = "deserialize_string_or_number")]
     pub shikimori_id: Option<String>,
 
     #[serde(rename = "type")]
@@ -157,10 +378,11 @@ pub struct Release {
     pub updated_at: String,
 
     /// If the series is blocked entirely, this field contains the string `"all"`. If individual seasons are blocked, the field is an object containing season numbers, and for each season: either `"all"` (if all episodes are blocked) or an array of episode numbers `["1", "2", "3"]` (if individual episodes are blocked). If nothing is blocked, the field is an empty object. This field is present only in materials with the series type.
-    pub blocked_seasons: Option<BTreeMap<String, BlockedSeason>>,
+    #[serde(default)]
+    pub blocked_seasons: BlockedSeasons,
 
     /// Object with seasons and episodes in them. This field is present only if the parameters `with_seasons` or `with_episodes`, `with_episodes_data` were specified in the request.
-    pub seasons: Option<BTreeMap<String, Season>>,
+    pub seasons: Option<BTreeMap<NumericKey, Season>>,
 
     /// Number of the last season of the series. This field is present only in materials with the series type.
     pub last_season: Option<i32>,
@@ -176,31 +398,694 @@ pub struct Release {
 
     pub material_data: Option<MaterialData>,
 
-    /// Links to frames from the video. For series, frames from the first episode are displayed in the main information. To get frames from each episode, use the `with_episodes_data`.
+    /// Links to frames from the video. For series, frames from the first episode are displayed in the main information. To get frames from each episode, use the `with_episodes_data`. Empty if frames are disabled with `with_screenshots`.
+    #[serde(default)]
     pub screenshots: Vec<String>,
 }
 
+impl Release {
+    /// Parses `id` into its kind and number
+    ///
+    /// Every translation of a title is a separate release, so the parsed ID identifies the release rather than the title.
+    pub fn parsed_id(&self) -> Result<ReleaseId, Error> {
+        self.id.parse()
+    }
+
+    /// Returns external identifiers of the release in a normalized form
+    ///
+    /// Empty values are dropped, IMDb IDs always carry the `tt` prefix and the World Art link is parsed into a section and a numeric ID.
+    pub fn external_ids(&self) -> ExternalIds {
+        ExternalIds {
+            kinopoisk: normalize_external_id(self.kinopoisk_id.as_deref()),
+            imdb: normalize_external_id(self.imdb_id.as_deref()).map(|imdb_id| {
+                if imdb_id.to_ascii_lowercase().starts_with("tt") {
+                    format!("tt{}", &imdb_id[2..])
+                } else {
+                    format!("tt{imdb_id}")
+                }
+            }),
+            mdl: normalize_external_id(self.mdl_id.as_deref()),
+            shikimori: normalize_external_id(self.shikimori_id.as_deref()),
+            worldart: self
+                .worldart_link
+                .as_deref()
+                .and_then(WorldartId::from_link),
+        }
+    }
+
+    /// Checks whether the whole series is blocked, see [`Release::blocked_seasons`]
+    pub fn is_fully_blocked(&self) -> bool {
+        self.blocked_seasons == BlockedSeasons::AllBlocked
+    }
+
+    /// Returns the link as an absolute URL
+    ///
+    /// The link leads to a page with a player hosted by Kodik only if the release was fetched with `with_page_links` (see [`crate::search::SearchQuery::hosted_page_params`]), otherwise it is a player link. Protocol-relative links are resolved with `https`.
+    ///
+    /// The page link can't be derived from a player link: Kodik doesn't document how they relate, so the page is only available from a request with `with_page_links`.
+    pub fn page_url(&self) -> Option<url::Url> {
+        url::Url::parse(&absolute_link(self.link.trim())).ok()
+    }
+
+    /// Returns the link with the `https:` scheme if it is protocol-relative, see [`absolute_link`]
+    pub fn absolute_link(&self) -> String {
+        absolute_link(&self.link)
+    }
+
+    /// Returns the player link with the quality segment replaced, see [`LinkQuality`]
+    pub fn link_with_quality(&self, quality: LinkQuality) -> String {
+        quality.apply(&self.link)
+    }
+
+    /// Returns the release type reconciled with the material data
+    ///
+    /// Kodik and Shikimori may disagree on whether an anime is a movie or a series. The first applicable rule wins:
+    ///
+    /// 1. Only anime releases are reconciled, other release types are returned as is
+    /// 2. `material_data.anime_kind`: `movie` is [`ReleaseType::Anime`], `tv`, `tv_13`, `tv_24`, `tv_48` and `ona` are [`ReleaseType::AnimeSerial`], `ova`, `special`, `music` and unknown kinds are ambiguous and skipped
+    /// 3. `material_data.all_status`: `ongoing` is [`ReleaseType::AnimeSerial`], since a movie cannot be ongoing
+    /// 4. `release_type`
+    ///
+    /// Material data is present only if the release was fetched with `with_material_data`.
+    pub fn effective_kind(&self) -> ReleaseType {
+        if self.release_type.category() != Some(Category::Anime) {
+            return self.release_type.clone();
+        }
+
+        let Some(material_data) = &self.material_data else {
+            return self.release_type.clone();
+        };
+
+        match &material_data.anime_kind {
+            Some(AnimeKind::Movie) => return ReleaseType::Anime,
+            Some(
+                AnimeKind::Tv
+                | AnimeKind::Tv13
+                | AnimeKind::Tv24
+                | AnimeKind::Tv48
+                | AnimeKind::Ona,
+            ) => return ReleaseType::AnimeSerial,
+            Some(AnimeKind::Ova | AnimeKind::Special | AnimeKind::Music | AnimeKind::Other(_))
+            | None => {}
+        }
+
+        if let Some(AllStatus::Ongoing) = material_data.all_status {
+            return ReleaseType::AnimeSerial;
+        }
+
+        self.release_type.clone()
+    }
+
+    /// Checks whether all declared episodes are available in `seasons`
+    ///
+    /// Only meaningful if the release was fetched with `with_episodes` or `with_episodes_data`. Returns `None` if there is no episode data or the release does not declare its episodes, which is always the case for movies.
+    pub fn is_complete(&self) -> Option<bool> {
+        let seasons = self.seasons.as_ref()?;
+
+        let available_episodes: usize = seasons.values().map(|season| season.episodes.len()).sum();
+
+        if available_episodes == 0 {
+            return None;
+        }
+
+        if let Some(episodes_count) = self.episodes_count {
+            return Some(available_episodes >= usize::try_from(episodes_count).unwrap_or_default());
+        }
+
+        let last_season = NumericKey::from(self.last_season?.to_string());
+        let last_episode = NumericKey::from(self.last_episode?.to_string());
+
+        Some(
+            seasons
+                .get(&last_season)
+                .is_some_and(|season| season.episodes.contains_key(&last_episode)),
+        )
+    }
+
+    /// Returns the number of seasons from the most authoritative source
+    ///
+    /// Sources in order of preference: `seasons` if it was requested and isn't empty, `last_season`.
+    pub fn season_count(&self) -> Option<u32> {
+        if let Some(seasons) = self.seasons.as_ref().filter(|seasons| !seasons.is_empty()) {
+            return u32::try_from(seasons.len()).ok();
+        }
+
+        self.last_season
+            .and_then(|last_season| u32::try_from(last_season).ok())
+    }
+
+    /// Returns the total number of episodes from the most authoritative source
+    ///
+    /// Sources in order of preference: episodes in `seasons` if they were requested with `with_episodes` or `with_episodes_data`, `episodes_count`, `material_data.episodes_total`.
+    pub fn total_episode_count(&self) -> Option<u32> {
+        let available_episodes: usize = self
+            .seasons
+            .iter()
+            .flat_map(|seasons| seasons.values())
+            .map(|season| season.episodes.len())
+            .sum();
+
+        if available_episodes > 0 {
+            return u32::try_from(available_episodes).ok();
+        }
+
+        self.episodes_count
+            .or_else(|| {
+                self.material_data
+                    .as_ref()
+                    .and_then(|material_data| material_data.episodes_total)
+            })
+            .and_then(|episodes_count| u32::try_from(episodes_count).ok())
+    }
+
+    /// Returns the links of all episodes across all seasons, in the order of seasons and episodes
+    ///
+    /// Built on [`unify_seasons`], so it works whether or not the seasons were requested. A movie or a release without seasons yields a single entry with its own link.
+    pub fn episode_links(&self) -> impl Iterator<Item = EpisodeLink> {
+        unify_seasons(self)
+            .into_iter()
+            .flat_map(|(season_num, season)| {
+                season
+                    .episodes
+                    .into_iter()
+                    .map(move |(episode_num, episode)| EpisodeLink {
+                        season: season_num.to_string(),
+                        episode: episode_num.into(),
+                        title: episode.title,
+                        link: episode.link,
+                    })
+            })
+    }
+
+    /// Returns the number of aired episodes and the total number of episodes, for example `(12, 24)`
+    ///
+    /// The values of `material_data` take precedence over the release ones. The aired episodes come from `material_data.episodes_aired`, `last_episode` or `episodes_count`, and the total from `material_data.episodes_total`, `episodes_count` or `last_episode`, in that order. A total of `0` in `material_data` means it is unknown and is skipped. Returns `None` if either value is missing.
+    pub fn episode_progress(&self) -> Option<(u32, u32)> {
+        let material_data = self.material_data.as_ref();
+
+        let aired = material_data
+            .and_then(|material_data| material_data.episodes_aired)
+            .or(self.last_episode)
+            .or(self.episodes_count)?;
+
+        let total = material_data
+            .and_then(|material_data| material_data.episodes_total)
+            .filter(|episodes_total| *episodes_total > 0)
+            .or(self.episodes_count)
+            .or(self.last_episode)?;
+
+        Some((u32::try_from(aired).ok()?, u32::try_from(total).ok()?))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Release {
+    /// Parses `created_at`
+    pub fn created_at_dt(&self) -> Result<DateTime<Utc>, Error> {
+        parse_date_time(&self.created_at)
+    }
+
+    /// Parses `updated_at`
+    pub fn updated_at_dt(&self) -> Result<DateTime<Utc>, Error> {
+        parse_date_time(&self.updated_at)
+    }
+
+    /// Returns `updated_at`, falling back to `created_at` if it can't be parsed, for sorting releases
+    ///
+    /// Returns `None` if neither can be parsed, so that such releases can be sorted last.
+    pub fn sort_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.updated_at_dt().or_else(|_| self.created_at_dt()).ok()
+    }
+}
+
+/// Deserializes an optional string which the API sometimes sends as a number
+fn deserialize_string_or_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    struct StringOrNumberVisitor;
+
+    impl<'de> Visitor<'de> for StringOrNumberVisitor {
+        type Value = Option<String>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a string, a number or null")
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_any(self)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            Ok(Some(value.to_owned()))
+        }
+
+        fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+            Ok(Some(value))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            Ok(Some(value.to_string()))
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            Ok(Some(value.to_string()))
+        }
+    }
+
+    deserializer.deserialize_option(StringOrNumberVisitor)
+}
+
+fn normalize_external_id(id: Option<&str>) -> Option<String> {
+    let id = id?.trim();
+
+    if id.is_empty() {
+        return None;
+    }
+
+    Some(id.to_owned())
+}
+
+/// Represents normalized external identifiers of a release
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExternalIds {
+    /// `"43949"`
+    pub kinopoisk: Option<String>,
+
+    /// `"tt0084716"`
+    pub imdb: Option<String>,
+
+    /// `"1245"`
+    pub mdl: Option<String>,
+
+    /// `"1234"`
+    pub shikimori: Option<String>,
+
+    pub worldart: Option<WorldartId>,
+}
+
+/// Represents a World Art identifier
+///
+/// World Art has different content sections, each with their own independent IDs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WorldartId {
+    /// `"animation"`, `"cinema"`
+    pub section: String,
+
+    /// `10534`
+    pub id: u64,
+}
+
+impl WorldartId {
+    /// Parses a World Art link like `"http://www.world-art.ru/animation/animation.php?id=10534"`
+    pub fn from_link(link: &str) -> Option<WorldartId> {
+        let url = url::Url::parse(link.trim()).ok()?;
+
+        let section = url.path_segments()?.next()?.to_owned();
+
+        if section.is_empty() {
+            return None;
+        }
+
+        let id = url
+            .query_pairs()
+            .find(|(key, _)| key == "id")?
+            .1
+            .parse()
+            .ok()?;
+
+        Some(WorldartId { section, id })
+    }
+}
+
+/// Represents the kind prefix of a release ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReleaseIdKind {
+    /// `movie-`
+    Movie,
+    /// `serial-`
+    Serial,
+}
+
+/// Represents a parsed release ID like `"movie-452654"` or `"serial-45534"`
+///
+/// ```
+/// use kodik_api::types::{ReleaseId, ReleaseIdKind};
+///
+/// let id: ReleaseId = "serial-45534".parse().unwrap();
+///
+/// assert_eq!(id.kind, ReleaseIdKind::Serial);
+/// assert_eq!(id.number, 45534);
+/// assert_eq!(id.to_string(), "serial-45534");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReleaseId {
+    pub kind: ReleaseIdKind,
+
+    pub number: u64,
+}
+
+impl FromStr for ReleaseId {
+    type Err = Error;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidParameter(format!("invalid release id {id:?}"));
+
+        let (kind, number) = id.split_once('-').ok_or_else(invalid)?;
+
+        let kind = match kind {
+            "movie" => ReleaseIdKind::Movie,
+            "serial" => ReleaseIdKind::Serial,
+            _ => return Err(invalid()),
+        };
+
+        // `u64::from_str` accepts a leading `+`, which is not a part of valid IDs
+        if !number.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let number = number.parse().map_err(|_| invalid())?;
+
+        Ok(ReleaseId { kind, number })
+    }
+}
+
+impl fmt::Display for ReleaseId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            ReleaseIdKind::Movie => "movie",
+            ReleaseIdKind::Serial => "serial",
+        };
+
+        write!(f, "{kind}-{}", self.number)
+    }
+}
+
+/// Represents the quality segment at the end of player links like `"//kodik.info/serial/45534/d8619e900d12/720p"`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkQuality {
+    #[serde(rename = "360p")]
+    P360,
+    #[serde(rename = "480p")]
+    P480,
+    #[serde(rename = "720p")]
+    P720,
+    #[serde(rename = "1080p")]
+    P1080,
+}
+
+impl LinkQuality {
+    /// Returns the segment as it appears in links
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkQuality::P360 => "360p",
+            LinkQuality::P480 => "480p",
+            LinkQuality::P720 => "720p",
+            LinkQuality::P1080 => "1080p",
+        }
+    }
+
+    /// Replaces the quality segment of the link, keeping its query
+    ///
+    /// The last path segment is recognized as a quality if it is a number followed by `p`. A link without it is returned unchanged.
+    ///
+    /// ```
+    /// use kodik_api::types::LinkQuality;
+    ///
+    /// assert_eq!(
+    ///     LinkQuality::P1080.apply("//kodik.info/season/45534/d8619e900d12/720p?season=1"),
+    ///     "//kodik.info/season/45534/d8619e900d12/1080p?season=1"
+    /// );
+    /// ```
+    pub fn apply(&self, link: &str) -> String {
+        let (path, query) = match link.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (link, None),
+        };
+
+        let Some((base, segment)) = path.rsplit_once('/') else {
+            return link.to_owned();
+        };
+
+        let is_quality = segment.strip_suffix('p').is_some_and(|height| {
+            !height.is_empty() && height.bytes().all(|byte| byte.is_ascii_digit())
+        });
+
+        if !is_quality {
+            return link.to_owned();
+        }
+
+        match query {
+            Some(query) => format!("{base}/{}?{query}", self.as_str()),
+            None => format!("{base}/{}", self.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for LinkQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Represents the blocked seasons of a release on Kodik
+///
+/// The string `"all"` is deserialized as [`BlockedSeasons::AllBlocked`], an object as [`BlockedSeasons::PerSeason`] and a missing field or `null` as [`BlockedSeasons::None`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum BlockedSeasons {
+    /// `"all"`, the whole series is blocked
+    AllBlocked,
+
+    /// Blocked seasons by their numbers, empty if nothing is blocked
+    PerSeason(BTreeMap<String, BlockedSeason>),
+
+    /// The release is not a series
+    #[default]
+    None,
+}
+
+impl BlockedSeasons {
+    /// Returns how the season with the given number is blocked, or `None` if it isn't
+    pub fn season(&self, season_num: &str) -> Option<&BlockedSeason> {
+        match self {
+            BlockedSeasons::AllBlocked => Some(&BlockedSeason::All),
+            BlockedSeasons::PerSeason(blocked_seasons) => blocked_seasons.get(season_num),
+            BlockedSeasons::None => None,
+        }
+    }
+}
+
+impl Serialize for BlockedSeasons {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BlockedSeasons::AllBlocked => serializer.serialize_str("all"),
+            BlockedSeasons::PerSeason(blocked_seasons) => blocked_seasons.serialize(serializer),
+            BlockedSeasons::None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockedSeasons {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BlockedSeasonsVisitor;
+
+        impl<'de> Visitor<'de> for BlockedSeasonsVisitor {
+            type Value = BlockedSeasons;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("\"all\" or an object of blocked seasons")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                match value {
+                    "all" => Ok(BlockedSeasons::AllBlocked),
+                    _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+                }
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                BTreeMap::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(BlockedSeasons::PerSeason)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(BlockedSeasons::None)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(BlockedSeasons::None)
+            }
+        }
+
+        deserializer.deserialize_any(BlockedSeasonsVisitor)
+    }
+}
+
 /// Represents a release blocked season on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// The string `"all"` is deserialized as [`BlockedSeason::All`] and an array of episode numbers as [`BlockedSeason::Episodes`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum BlockedSeason {
     All,
 
     Episodes(Vec<String>),
 }
 
+impl Serialize for BlockedSeason {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BlockedSeason::All => serializer.serialize_str("all"),
+            BlockedSeason::Episodes(episodes) => episodes.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockedSeason {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BlockedSeasonVisitor;
+
+        impl<'de> Visitor<'de> for BlockedSeasonVisitor {
+            type Value = BlockedSeason;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("\"all\" or an array of episode numbers")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                match value {
+                    "all" => Ok(BlockedSeason::All),
+                    _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+                }
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                Vec::deserialize(de::value::SeqAccessDeserializer::new(seq))
+                    .map(BlockedSeason::Episodes)
+            }
+        }
+
+        deserializer.deserialize_any(BlockedSeasonVisitor)
+    }
+}
+
+/// Number of a season or an episode in [`Release::seasons`], ordered numerically
+///
+/// Kodik numbers seasons and episodes with strings, so as plain strings `"10"` would go before `"2"`. Numeric keys are ordered by their value and go before the other ones, which are ordered as strings.
+///
+/// ```
+/// use kodik_api::types::NumericKey;
+///
+/// assert!(NumericKey::from("2") < NumericKey::from("10"));
+/// assert!(NumericKey::from("10") < NumericKey::from("special"));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct NumericKey(String);
+
+impl NumericKey {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the number, or `None` if the key is not a number
+    pub fn number(&self) -> Option<u32> {
+        self.0.parse().ok()
+    }
+}
+
+impl Ord for NumericKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self.number(), other.number()) {
+            (Some(number), Some(other_number)) => {
+                number.cmp(&other_number).then_with(|| self.0.cmp(&other.0))
+            }
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => self.0.cmp(&other.0),
+        }
+    }
+}
+
+impl PartialOrd for NumericKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<String> for NumericKey {
+    fn from(value: String) -> Self {
+        NumericKey(value)
+    }
+}
+
+impl From<&str> for NumericKey {
+    fn from(value: &str) -> Self {
+        NumericKey(value.to_owned())
+    }
+}
+
+impl From<u32> for NumericKey {
+    fn from(value: u32) -> Self {
+        NumericKey(value.to_string())
+    }
+}
+
+impl From<NumericKey> for String {
+    fn from(value: NumericKey) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<str> for NumericKey {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for NumericKey {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl fmt::Display for NumericKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// Represents a release season object on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Season {
     /// For example, it can be marked as a recap, special, etc.
     pub title: Option<String>,
 
     pub link: String,
 
-    pub episodes: BTreeMap<String, EpisodeUnion>,
+    pub episodes: BTreeMap<NumericKey, EpisodeUnion>,
+}
+
+impl Season {
+    /// Returns the link with the `https:` scheme if it is protocol-relative, see [`absolute_link`]
+    pub fn absolute_link(&self) -> String {
+        absolute_link(&self.link)
+    }
 }
 
 /// Represents a release episode on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// A string is deserialized as a link and an object as an episode, anything else is an error
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum EpisodeUnion {
     /// `"http://kodik.cc/seria/119611/09249413a7eb3c03b15df57cd56a051b/720p"`
@@ -209,8 +1094,37 @@ pub enum EpisodeUnion {
     Episode(Episode),
 }
 
+impl<'de> Deserialize<'de> for EpisodeUnion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EpisodeUnionVisitor;
+
+        impl<'de> Visitor<'de> for EpisodeUnionVisitor {
+            type Value = EpisodeUnion;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an episode link or an episode object")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(EpisodeUnion::Link(value.to_owned()))
+            }
+
+            fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+                Ok(EpisodeUnion::Link(value))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                Episode::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(EpisodeUnion::Episode)
+            }
+        }
+
+        deserializer.deserialize_any(EpisodeUnionVisitor)
+    }
+}
+
 /// Represents a release episode object on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Episode {
     /// For example, it сan be marked as special
     pub title: Option<String>,
@@ -218,11 +1132,37 @@ pub struct Episode {
     /// `"http://kodik.cc/seria/119611/09249413a7eb3c03b15df57cd56a051b/720p"`
     pub link: String,
 
+    /// Empty if frames are disabled with `with_episode_screenshots`
+    #[serde(default)]
     pub screenshots: Vec<String>,
 }
 
+impl Episode {
+    /// Returns the link with the `https:` scheme if it is protocol-relative, see [`absolute_link`]
+    pub fn absolute_link(&self) -> String {
+        absolute_link(&self.link)
+    }
+}
+
+/// Prepends `https:` to a protocol-relative link like `"//kodik.info/serial/45534/d8619e900d12/720p"`
+///
+/// Links with a scheme are returned unchanged.
+///
+/// ```
+/// use kodik_api::types::absolute_link;
+///
+/// assert_eq!(absolute_link("//kodik.info/seria/1/2/720p"), "https://kodik.info/seria/1/2/720p");
+/// assert_eq!(absolute_link("http://kodik.cc/seria/1/2/720p"), "http://kodik.cc/seria/1/2/720p");
+/// ```
+pub fn absolute_link(link: &str) -> String {
+    match link.strip_prefix("//") {
+        Some(link) => format!("https://{link}"),
+        None => link.to_owned(),
+    }
+}
+
 /// Represents a release translation type on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TranslationType {
     #[serde(rename = "subtitles")]
     Subtitles,
@@ -231,8 +1171,35 @@ pub enum TranslationType {
     Voice,
 }
 
+/// Represents an entry of the translation priority in search queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TranslationPriority {
+    /// Translation ID, see [`crate::translations::TranslationQuery`]
+    Id(u32),
+
+    /// All voice translations
+    Voice,
+
+    /// All subtitles
+    Subtitles,
+
+    /// Disables the standard priority
+    Disable,
+}
+
+impl Serialize for TranslationPriority {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            TranslationPriority::Id(id) => serializer.serialize_u32(*id),
+            TranslationPriority::Voice => serializer.serialize_str("voice"),
+            TranslationPriority::Subtitles => serializer.serialize_str("subtitles"),
+            TranslationPriority::Disable => serializer.serialize_str("0"),
+        }
+    }
+}
+
 /// Represents a release translation on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Translation {
     pub id: i32,
 
@@ -245,7 +1212,7 @@ pub struct Translation {
 }
 
 /// Represents a release anime kind on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AnimeKind {
     #[serde(rename = "tv")]
     Tv,
@@ -265,10 +1232,31 @@ pub enum AnimeKind {
     Tv24,
     #[serde(rename = "tv_48")]
     Tv48,
+    /// A kind this version of the crate doesn't know yet, with the raw value
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl AnimeKind {
+    /// Returns the value as it is sent and received by the API
+    pub fn as_wire_str(&self) -> &str {
+        match self {
+            AnimeKind::Tv => "tv",
+            AnimeKind::Movie => "movie",
+            AnimeKind::Ova => "ova",
+            AnimeKind::Ona => "ona",
+            AnimeKind::Special => "special",
+            AnimeKind::Music => "music",
+            AnimeKind::Tv13 => "tv_13",
+            AnimeKind::Tv24 => "tv_24",
+            AnimeKind::Tv48 => "tv_48",
+            AnimeKind::Other(value) => value,
+        }
+    }
 }
 
 /// Represents a release all kind on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AllStatus {
     #[serde(rename = "anons")]
     Anons,
@@ -276,10 +1264,25 @@ pub enum AllStatus {
     Ongoing,
     #[serde(rename = "released")]
     Released,
+    /// A status this version of the crate doesn't know yet, with the raw value
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl AllStatus {
+    /// Returns the value as it is sent and received by the API
+    pub fn as_wire_str(&self) -> &str {
+        match self {
+            AllStatus::Anons => "anons",
+            AllStatus::Ongoing => "ongoing",
+            AllStatus::Released => "released",
+            AllStatus::Other(value) => value,
+        }
+    }
 }
 
 /// Represents a release anime status on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AnimeStatus {
     #[serde(rename = "anons")]
     Anons,
@@ -287,10 +1290,25 @@ pub enum AnimeStatus {
     Ongoing,
     #[serde(rename = "released")]
     Released,
+    /// A status this version of the crate doesn't know yet, with the raw value
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl AnimeStatus {
+    /// Returns the value as it is sent and received by the API
+    pub fn as_wire_str(&self) -> &str {
+        match self {
+            AnimeStatus::Anons => "anons",
+            AnimeStatus::Ongoing => "ongoing",
+            AnimeStatus::Released => "released",
+            AnimeStatus::Other(value) => value,
+        }
+    }
 }
 
 /// Represents a release drama status on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DramaStatus {
     #[serde(rename = "anons")]
     Anons,
@@ -298,10 +1316,25 @@ pub enum DramaStatus {
     Ongoing,
     #[serde(rename = "released")]
     Released,
+    /// A status this version of the crate doesn't know yet, with the raw value
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl DramaStatus {
+    /// Returns the value as it is sent and received by the API
+    pub fn as_wire_str(&self) -> &str {
+        match self {
+            DramaStatus::Anons => "anons",
+            DramaStatus::Ongoing => "ongoing",
+            DramaStatus::Released => "released",
+            DramaStatus::Other(value) => value,
+        }
+    }
 }
 
 /// Represents a release MPPA rating on Kodik
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MppaRating {
     /// `0+ `
     #[serde(rename = "G")]
@@ -321,10 +1354,28 @@ pub enum MppaRating {
     /// `21+`
     #[serde(rename = "Rx")]
     Rx,
+    /// A rating this version of the crate doesn't know yet, with the raw value
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl MppaRating {
+    /// Returns the value as it is sent and received by the API
+    pub fn as_wire_str(&self) -> &str {
+        match self {
+            MppaRating::G => "G",
+            MppaRating::Pg => "PG",
+            MppaRating::Pg13 => "PG-13",
+            MppaRating::R => "R",
+            MppaRating::RPlus => "R+",
+            MppaRating::Rx => "Rx",
+            MppaRating::Other(value) => value,
+        }
+    }
 }
 
 /// Represents a release material data field
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MaterialDataField {
     #[serde(rename = "kinopoisk_id")]
     /// kinopoisk_id
@@ -344,7 +1395,9 @@ pub enum MaterialDataField {
 }
 
 /// Represents various data related to a material, such as title, description, ratings, etc.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// Only `PartialEq` and not `Eq`, since the ratings are floats.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct MaterialData {
     /// `"Аватар"`
     ///
@@ -618,4 +1671,1338 @@ pub struct MaterialData {
     ///
     /// Source: `KinoPoisk`, `MyDramaList`
     pub operators: Option<Vec<String>>,
+
+    /// Fields that are not mapped to the fields above yet, for example ones recently added by Kodik
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+#[cfg(feature = "chrono")]
+impl MaterialData {
+    /// Parses `premiere_ru`
+    pub fn premiere_ru_date(&self) -> Result<Option<NaiveDate>, Error> {
+        parse_date(self.premiere_ru.as_deref())
+    }
+
+    /// Parses `premiere_world`
+    pub fn premiere_world_date(&self) -> Result<Option<NaiveDate>, Error> {
+        parse_date(self.premiere_world.as_deref())
+    }
+
+    /// Parses `aired_at`
+    pub fn aired_at_date(&self) -> Result<Option<NaiveDate>, Error> {
+        parse_date(self.aired_at.as_deref())
+    }
+
+    /// Parses `released_at`
+    pub fn released_at_date(&self) -> Result<Option<NaiveDate>, Error> {
+        parse_date(self.released_at.as_deref())
+    }
+
+    /// Parses `next_episode_at`
+    pub fn next_episode_at_dt(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        self.next_episode_at
+            .as_deref()
+            .map(parse_date_time)
+            .transpose()
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn parse_date(value: Option<&str>) -> Result<Option<NaiveDate>, Error> {
+    value
+        .map(|value| value.parse().map_err(Error::DateParseError))
+        .transpose()
+}
+
+#[cfg(feature = "chrono")]
+fn parse_date_time(value: &str) -> Result<DateTime<Utc>, Error> {
+    value.parse().map_err(Error::DateParseError)
+}
+
+/// A release year, from 1900 up to five years after the current one
+///
+/// ```
+/// use kodik_api::types::Year;
+///
+/// assert_eq!(Year::new(2022).unwrap().to_string(), "2022");
+/// assert!(Year::new(12).is_err());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(try_from = "u16", into = "u16")]
+pub struct Year(u16);
+
+impl Year {
+    /// The earliest accepted year
+    pub const MIN: u16 = 1900;
+
+    /// Constructs a new `Year`
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidParameter`] if the year is before [`Year::MIN`] or more than five years after the current one
+    pub fn new(year: u16) -> Result<Year, Error> {
+        let max = Year::current() + 5;
+
+        if !(Year::MIN..=max).contains(&year) {
+            return Err(Error::InvalidParameter(format!(
+                "year {year} is out of range {}..={max}",
+                Year::MIN
+            )));
+        }
+
+        Ok(Year(year))
+    }
+
+    /// Returns the year as a number
+    pub fn get(self) -> u16 {
+        self.0
+    }
+
+    // Approximated with the average length of a Gregorian year, which is good enough for an upper bound
+    fn current() -> u16 {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        1970 + (seconds / 31_556_952) as u16
+    }
+}
+
+impl TryFrom<u16> for Year {
+    type Error = Error;
+
+    fn try_from(year: u16) -> Result<Self, Self::Error> {
+        Year::new(year)
+    }
+}
+
+impl From<Year> for u16 {
+    fn from(year: Year) -> Self {
+        year.0
+    }
+}
+
+impl fmt::Display for Year {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A rating filter, either an exact value or an interval
+///
+/// Kodik expects an interval as `min-max`, so an open end is filled with the bound of the ratings scale, `0` or `10`.
+///
+/// ```
+/// use kodik_api::types::Range;
+///
+/// assert_eq!(Range::Exact(7.5).to_string(), "7.5");
+/// assert_eq!(Range::Between(6.5, 8.0).to_string(), "6.5-8");
+/// assert_eq!(Range::AtLeast(7.0).to_string(), "7-10");
+/// assert_eq!(Range::AtMost(5.5).to_string(), "0-5.5");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Range {
+    Exact(f32),
+    Between(f32, f32),
+    AtLeast(f32),
+    AtMost(f32),
+}
+
+impl Range {
+    /// The lowest rating
+    pub const MIN: f32 = 0.0;
+
+    /// The highest rating
+    pub const MAX: f32 = 10.0;
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Range::Exact(value) => write!(f, "{value}"),
+            Range::Between(min, max) => write!(f, "{min}-{max}"),
+            Range::AtLeast(min) => write!(f, "{min}-{}", Range::MAX),
+            Range::AtMost(max) => write!(f, "{}-{max}", Range::MIN),
+        }
+    }
+}
+
+/// A duration filter in minutes, either an exact value or an interval
+///
+/// Kodik expects an interval as `min-max`, so an open end is filled with `0` or [`DurationRange::MAX`].
+///
+/// ```
+/// use kodik_api::types::DurationRange;
+///
+/// assert_eq!(DurationRange::Exact(24).to_string(), "24");
+/// assert_eq!(DurationRange::Between(20, 30).to_string(), "20-30");
+/// assert_eq!(DurationRange::AtMost(30).to_string(), "0-30");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DurationRange {
+    Exact(u32),
+    Between(u32, u32),
+    AtLeast(u32),
+    AtMost(u32),
+}
+
+impl DurationRange {
+    /// The upper end of [`DurationRange::AtLeast`], far longer than any release
+    pub const MAX: u32 = u16::MAX as u32;
+}
+
+impl fmt::Display for DurationRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurationRange::Exact(value) => write!(f, "{value}"),
+            DurationRange::Between(min, max) => write!(f, "{min}-{max}"),
+            DurationRange::AtLeast(min) => write!(f, "{min}-{}", DurationRange::MAX),
+            DurationRange::AtMost(max) => write!(f, "0-{max}"),
+        }
+    }
+}
+
+/// A country code in the ISO 3166-1 alpha-2 format, for example `RU`
+///
+/// Only the format is checked, two uppercase latin letters, not whether the country exists.
+///
+/// ```
+/// use kodik_api::types::CountryCode;
+///
+/// assert_eq!(CountryCode::new("RU").unwrap().to_string(), "RU");
+/// assert!(CountryCode::new("Ru").is_err());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct CountryCode([u8; 2]);
+
+impl CountryCode {
+    /// Constructs a new `CountryCode`
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidParameter`] if the code is not two uppercase latin letters
+    pub fn new(code: &str) -> Result<CountryCode, Error> {
+        match code.as_bytes() {
+            &[first, second] if first.is_ascii_uppercase() && second.is_ascii_uppercase() => {
+                Ok(CountryCode([first, second]))
+            }
+            _ => Err(Error::InvalidParameter(format!(
+                "country code {code:?} is not two uppercase latin letters"
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("country code must be ASCII")
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = Error;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        CountryCode::new(code)
+    }
+}
+
+impl TryFrom<String> for CountryCode {
+    type Error = Error;
+
+    fn try_from(code: String) -> Result<Self, Self::Error> {
+        CountryCode::new(&code)
+    }
+}
+
+impl From<CountryCode> for String {
+    fn from(code: CountryCode) -> Self {
+        code.as_str().to_owned()
+    }
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Conversion of borrowed or owned lists into values of a query parameter
+///
+/// Implemented for slices, arrays and vectors, and for lists of `&str` or `String` as string values.
+pub trait IntoQueryList<'a, T: Clone> {
+    fn into_query_list(self) -> Cow<'a, [T]>;
+}
+
+impl<'a, T: Clone> IntoQueryList<'a, T> for &'a [T] {
+    fn into_query_list(self) -> Cow<'a, [T]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a, T: Clone, const N: usize> IntoQueryList<'a, T> for &'a [T; N] {
+    fn into_query_list(self) -> Cow<'a, [T]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a, T: Clone> IntoQueryList<'a, T> for &'a Vec<T> {
+    fn into_query_list(self) -> Cow<'a, [T]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a, T: Clone> IntoQueryList<'a, T> for Vec<T> {
+    fn into_query_list(self) -> Cow<'a, [T]> {
+        Cow::Owned(self)
+    }
+}
+
+impl<'a> IntoQueryList<'a, Cow<'a, str>> for &'a [&'a str] {
+    fn into_query_list(self) -> Cow<'a, [Cow<'a, str>]> {
+        Cow::Owned(self.iter().map(|value| Cow::Borrowed(*value)).collect())
+    }
+}
+
+impl<'a, const N: usize> IntoQueryList<'a, Cow<'a, str>> for &'a [&'a str; N] {
+    fn into_query_list(self) -> Cow<'a, [Cow<'a, str>]> {
+        self.as_slice().into_query_list()
+    }
+}
+
+impl<'a> IntoQueryList<'a, Cow<'a, str>> for Vec<&'a str> {
+    fn into_query_list(self) -> Cow<'a, [Cow<'a, str>]> {
+        Cow::Owned(self.into_iter().map(Cow::Borrowed).collect())
+    }
+}
+
+impl<'a> IntoQueryList<'a, Cow<'a, str>> for &'a [String] {
+    fn into_query_list(self) -> Cow<'a, [Cow<'a, str>]> {
+        Cow::Owned(
+            self.iter()
+                .map(|value| Cow::Borrowed(value.as_str()))
+                .collect(),
+        )
+    }
+}
+
+impl<'a> IntoQueryList<'a, Cow<'a, str>> for &'a Vec<String> {
+    fn into_query_list(self) -> Cow<'a, [Cow<'a, str>]> {
+        self.as_slice().into_query_list()
+    }
+}
+
+impl<'a> IntoQueryList<'a, Cow<'a, str>> for Vec<String> {
+    fn into_query_list(self) -> Cow<'a, [Cow<'a, str>]> {
+        Cow::Owned(self.into_iter().map(Cow::Owned).collect())
+    }
+}
+
+/// A response which reports the time the server took to process the request
+///
+/// ```
+/// use kodik_api::{search::SearchQuery, types::ProcessingTime, Client};
+///
+/// # async fn run() {
+/// let client = Client::from_env().expect("KODIK_API_KEY is not set");
+///
+/// let response = SearchQuery::new()
+///     .with_title("Cyberpunk: Edgerunners")
+///     .execute(&client)
+///     .await
+///     .unwrap();
+///
+/// println!("processing time = {:?}", response.processing_time());
+/// # }
+/// ```
+pub trait ProcessingTime {
+    /// The time as reported by the API, for example `15ms`
+    fn time(&self) -> &str;
+
+    /// Parses [`ProcessingTime::time`] in the `<n>ms` or `<n>s` format, `None` if it has another format
+    fn processing_time(&self) -> Option<Duration> {
+        parse_processing_time(self.time())
+    }
+}
+
+fn parse_processing_time(time: &str) -> Option<Duration> {
+    let (value, unit) = match time.strip_suffix("ms") {
+        Some(value) => (value, 1e-3),
+        None => (time.strip_suffix('s')?, 1.0),
+    };
+
+    let value = value.trim().parse::<f64>().ok()?;
+
+    Duration::try_from_secs_f64(value * unit).ok()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    pub(crate) fn get_default_kodik_release() -> Release {
+        Release {
+            id: "serial-45534".to_owned(),
+            title: "Киберпанк: Бегущие по краю".to_owned(),
+            title_orig: "Cyberpunk: Edgerunners".to_owned(),
+            other_title: Some("サイバーパンク エッジランナーズ".to_owned()),
+            link: "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p".to_owned(),
+            year: 2022,
+            kinopoisk_id: Some("2000102".to_owned()),
+            imdb_id: Some("tt12590266".to_owned()),
+            mdl_id: None,
+            worldart_link: Some(
+                "http://www.world-art.ru/animation/animation.php?id=10534".to_owned(),
+            ),
+            shikimori_id: Some("42310".to_owned()),
+            release_type: ReleaseType::AnimeSerial,
+            quality: ReleaseQuality::WebDlRip720p,
+            camrip: false,
+            lgbt: false,
+            translation: Translation {
+                id: 610,
+                title: "AniLibria.TV".to_owned(),
+                translation_type: TranslationType::Voice,
+            },
+            created_at: "2022-09-14T10:54:34Z".to_owned(),
+            updated_at: "2022-09-23T22:31:33Z".to_owned(),
+            blocked_seasons: BlockedSeasons::PerSeason(BTreeMap::new()),
+            seasons: None,
+            last_season: Some(1),
+            last_episode: Some(10),
+            episodes_count: Some(10),
+            blocked_countries: vec![],
+            material_data: None,
+            screenshots: vec!["https://i.kodik.biz/screenshots/seria/104981222/1.jpg".to_owned()],
+        }
+    }
+
+    #[test]
+    fn test_external_ids() {
+        let kodik_release = get_default_kodik_release();
+
+        assert_eq!(
+            kodik_release.external_ids(),
+            ExternalIds {
+                kinopoisk: Some("2000102".to_owned()),
+                imdb: Some("tt12590266".to_owned()),
+                mdl: None,
+                shikimori: Some("42310".to_owned()),
+                worldart: Some(WorldartId {
+                    section: "animation".to_owned(),
+                    id: 10534,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn test_page_url() {
+        let mut kodik_release = get_default_kodik_release();
+
+        assert_eq!(
+            kodik_release.page_url().unwrap().as_str(),
+            "https://kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p"
+        );
+
+        kodik_release.link =
+            "http://kodik.cc/video/19850/6476310cc6d90aa9304d5d8af3a91279/720p".to_owned();
+
+        assert_eq!(
+            kodik_release.page_url().unwrap().as_str(),
+            "http://kodik.cc/video/19850/6476310cc6d90aa9304d5d8af3a91279/720p"
+        );
+
+        kodik_release.link = String::new();
+
+        assert_eq!(kodik_release.page_url(), None);
+    }
+
+    #[test]
+    fn test_category() {
+        let categories = [
+            Category::Anime,
+            Category::Movies,
+            Category::Series,
+            Category::Cartoons,
+            Category::Documentaries,
+        ];
+
+        for category in categories {
+            for release_type in category.release_types() {
+                assert_eq!(release_type.category(), Some(category));
+            }
+        }
+
+        assert_eq!(
+            categories
+                .iter()
+                .map(|category| category.release_types().len())
+                .sum::<usize>(),
+            12
+        );
+
+        assert_eq!(
+            Category::release_types_of(&[Category::Anime, Category::Movies, Category::Anime]),
+            vec![
+                ReleaseType::Anime,
+                ReleaseType::AnimeSerial,
+                ReleaseType::ForeignMovie,
+                ReleaseType::RussianMovie,
+                ReleaseType::MultiPartFilm,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_effective_kind() {
+        let cases = [
+            (
+                ReleaseType::AnimeSerial,
+                None,
+                None,
+                ReleaseType::AnimeSerial,
+            ),
+            (
+                ReleaseType::AnimeSerial,
+                Some(AnimeKind::Movie),
+                None,
+                ReleaseType::Anime,
+            ),
+            (
+                ReleaseType::AnimeSerial,
+                Some(AnimeKind::Movie),
+                Some(AllStatus::Ongoing),
+                ReleaseType::Anime,
+            ),
+            (
+                ReleaseType::Anime,
+                Some(AnimeKind::Tv),
+                Some(AllStatus::Released),
+                ReleaseType::AnimeSerial,
+            ),
+            (
+                ReleaseType::Anime,
+                Some(AnimeKind::Ona),
+                None,
+                ReleaseType::AnimeSerial,
+            ),
+            (
+                ReleaseType::Anime,
+                Some(AnimeKind::Ova),
+                None,
+                ReleaseType::Anime,
+            ),
+            (
+                ReleaseType::AnimeSerial,
+                Some(AnimeKind::Special),
+                None,
+                ReleaseType::AnimeSerial,
+            ),
+            (
+                ReleaseType::Anime,
+                Some(AnimeKind::Special),
+                Some(AllStatus::Ongoing),
+                ReleaseType::AnimeSerial,
+            ),
+            (
+                ReleaseType::Anime,
+                None,
+                Some(AllStatus::Anons),
+                ReleaseType::Anime,
+            ),
+            (
+                ReleaseType::ForeignMovie,
+                Some(AnimeKind::Tv),
+                Some(AllStatus::Ongoing),
+                ReleaseType::ForeignMovie,
+            ),
+        ];
+
+        for (release_type, anime_kind, all_status, expected) in cases {
+            let mut kodik_release = get_default_kodik_release();
+
+            kodik_release.release_type = release_type;
+            kodik_release.material_data = Some(MaterialData {
+                anime_kind,
+                all_status,
+                ..Default::default()
+            });
+
+            assert_eq!(kodik_release.effective_kind(), expected);
+        }
+
+        let mut kodik_release = get_default_kodik_release();
+
+        kodik_release.release_type = ReleaseType::Anime;
+
+        assert_eq!(kodik_release.effective_kind(), ReleaseType::Anime);
+    }
+
+    #[test]
+    fn test_unknown_release_type() {
+        let mut value = serde_json::to_value(get_default_kodik_release()).unwrap();
+
+        value["type"] = json!("foreign-anthology");
+
+        let release: Release = serde_json::from_value(value).unwrap();
+
+        assert_eq!(release.release_type, ReleaseType::Unknown);
+        assert_eq!(release.release_type.category(), None);
+    }
+
+    #[test]
+    fn test_year() {
+        let year = Year::new(2022).unwrap();
+
+        assert_eq!(year.get(), 2022);
+        assert!(Year::new(1899).is_err());
+        assert!(Year::new(Year::current() + 5).is_ok());
+        assert!(matches!(
+            Year::try_from(Year::current() + 6),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(Year::new(1999).unwrap() < year);
+
+        assert_eq!(serde_json::to_value(year).unwrap(), json!(2022));
+        assert_eq!(serde_json::from_value::<Year>(json!(2022)).unwrap(), year);
+        assert!(serde_json::from_value::<Year>(json!(0)).is_err());
+    }
+
+    #[test]
+    fn test_release_quality_order() {
+        let mut qualities = vec![
+            ReleaseQuality::Other("8K".to_owned()),
+            ReleaseQuality::WebDlRip720p,
+            ReleaseQuality::CamRip,
+            ReleaseQuality::DvdRip,
+            ReleaseQuality::BdRip1080p,
+        ];
+
+        qualities.sort();
+
+        assert_eq!(
+            qualities,
+            [
+                ReleaseQuality::Other("8K".to_owned()),
+                ReleaseQuality::CamRip,
+                ReleaseQuality::DvdRip,
+                ReleaseQuality::WebDlRip720p,
+                ReleaseQuality::BdRip1080p,
+            ]
+        );
+
+        assert!(ReleaseQuality::WebDlRip1080p > ReleaseQuality::HdRip720p);
+        assert!(ReleaseQuality::Ts720p < ReleaseQuality::VhsRip);
+        assert_eq!(
+            ReleaseQuality::BdRip1080p.resolution_rank(),
+            ReleaseQuality::WebDlRip1080p.resolution_rank()
+        );
+        assert_ne!(
+            ReleaseQuality::BdRip1080p.cmp(&ReleaseQuality::WebDlRip1080p),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_material_data_extra() {
+        let material_data: MaterialData = serde_json::from_value(json!({
+            "title": "Киберпанк: Бегущие по краю",
+            "episodes_total": 10,
+            "anime_poster_hd_url": "https://shikimori.one/system/animes/original/42310.jpg",
+        }))
+        .unwrap();
+
+        assert_eq!(
+            material_data.title.as_deref(),
+            Some("Киберпанк: Бегущие по краю")
+        );
+        assert_eq!(material_data.episodes_total, Some(10));
+        assert_eq!(
+            material_data.extra,
+            HashMap::from([(
+                "anime_poster_hd_url".to_owned(),
+                json!("https://shikimori.one/system/animes/original/42310.jpg")
+            )])
+        );
+
+        // Serialized back along with the typed fields
+        assert_eq!(
+            serde_json::to_value(&material_data).unwrap()["anime_poster_hd_url"],
+            json!("https://shikimori.one/system/animes/original/42310.jpg")
+        );
+    }
+
+    #[test]
+    fn test_ids_as_numbers() {
+        let mut value = serde_json::to_value(get_default_kodik_release()).unwrap();
+
+        for id in [json!("43949"), json!(43949)] {
+            value["kinopoisk_id"] = id.clone();
+            value["mdl_id"] = id.clone();
+            value["shikimori_id"] = id;
+
+            let release: Release = serde_json::from_value(value.clone()).unwrap();
+
+            assert_eq!(release.kinopoisk_id.as_deref(), Some("43949"));
+            assert_eq!(release.mdl_id.as_deref(), Some("43949"));
+            assert_eq!(release.shikimori_id.as_deref(), Some("43949"));
+        }
+
+        value["imdb_id"] = json!(null);
+        value.as_object_mut().unwrap().remove("mdl_id");
+
+        let release: Release = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(release.imdb_id, None);
+        assert_eq!(release.mdl_id, None);
+
+        value["kinopoisk_id"] = json!(true);
+
+        assert!(serde_json::from_value::<Release>(value).is_err());
+    }
+
+    #[test]
+    fn test_country_code() {
+        let code = CountryCode::new("RU").unwrap();
+
+        assert_eq!(code.as_str(), "RU");
+        assert_eq!("UA".parse::<CountryCode>().unwrap().to_string(), "UA");
+
+        for invalid in ["Ru", "ru", "RUS", "R", "", "Р1"] {
+            assert!(
+                matches!(CountryCode::new(invalid), Err(Error::InvalidParameter(_))),
+                "{invalid}"
+            );
+        }
+
+        assert_eq!(serde_json::to_value(code).unwrap(), json!("RU"));
+        assert_eq!(
+            serde_json::from_value::<CountryCode>(json!("RU")).unwrap(),
+            code
+        );
+        assert!(serde_json::from_value::<CountryCode>(json!("Ru")).is_err());
+    }
+
+    #[test]
+    fn test_other_material_data_values() {
+        let mut value = serde_json::to_value(get_default_kodik_release()).unwrap();
+
+        value["material_data"] = json!({
+            "anime_kind": "tv_72",
+            "anime_status": "paused",
+            "drama_status": "cancelled",
+            "all_status": "unknown",
+            "rating_mpaa": "NC-17",
+        });
+
+        let release: Release = serde_json::from_value(value).unwrap();
+        let material_data = release.material_data.unwrap();
+
+        assert_eq!(
+            material_data.anime_kind,
+            Some(AnimeKind::Other("tv_72".to_owned()))
+        );
+        assert_eq!(
+            material_data.anime_status,
+            Some(AnimeStatus::Other("paused".to_owned()))
+        );
+        assert_eq!(
+            material_data.drama_status,
+            Some(DramaStatus::Other("cancelled".to_owned()))
+        );
+        assert_eq!(
+            material_data.all_status,
+            Some(AllStatus::Other("unknown".to_owned()))
+        );
+        assert_eq!(
+            material_data.rating_mpaa,
+            Some(MppaRating::Other("NC-17".to_owned()))
+        );
+
+        for status in [AllStatus::Anons, AllStatus::Ongoing, AllStatus::Released] {
+            assert_eq!(
+                serde_json::from_value::<AllStatus>(json!(status.as_wire_str())).unwrap(),
+                status
+            );
+        }
+
+        for rating in [
+            MppaRating::G,
+            MppaRating::Pg,
+            MppaRating::Pg13,
+            MppaRating::R,
+            MppaRating::RPlus,
+            MppaRating::Rx,
+        ] {
+            assert_eq!(
+                serde_json::from_value::<MppaRating>(json!(rating.as_wire_str())).unwrap(),
+                rating
+            );
+        }
+
+        // The raw value is sent back as is when used as a filter
+        assert_eq!(
+            serde_json::to_value(MppaRating::Other("NC-17".to_owned())).unwrap(),
+            json!("NC-17")
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_dates() {
+        let mut release = get_default_kodik_release();
+
+        assert_eq!(
+            release.created_at_dt().unwrap().to_rfc3339(),
+            "2022-09-14T10:54:34+00:00"
+        );
+        assert_eq!(
+            release.updated_at_dt().unwrap().to_rfc3339(),
+            "2022-09-23T22:31:33+00:00"
+        );
+
+        assert_eq!(release.sort_timestamp(), release.updated_at_dt().ok());
+
+        release.updated_at = String::new();
+
+        assert_eq!(release.sort_timestamp(), release.created_at_dt().ok());
+
+        release.created_at = "yesterday".to_owned();
+
+        assert_eq!(release.sort_timestamp(), None);
+
+        let material_data = MaterialData {
+            premiere_ru: Some("2018-04-16".to_owned()),
+            next_episode_at: Some("2021-04-06T14:19:27Z".to_owned()),
+            released_at: Some("16.04.2018".to_owned()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            material_data.premiere_ru_date().unwrap(),
+            NaiveDate::from_ymd_opt(2018, 4, 16)
+        );
+        assert_eq!(material_data.aired_at_date().unwrap(), None);
+        assert!(matches!(
+            material_data.released_at_date(),
+            Err(Error::DateParseError(_))
+        ));
+        assert_eq!(
+            material_data
+                .next_episode_at_dt()
+                .unwrap()
+                .unwrap()
+                .to_rfc3339(),
+            "2021-04-06T14:19:27+00:00"
+        );
+    }
+
+    #[test]
+    fn test_release_id() {
+        let mut release = get_default_kodik_release();
+
+        release.id = "movie-452654".to_owned();
+
+        assert_eq!(
+            release.parsed_id().unwrap(),
+            ReleaseId {
+                kind: ReleaseIdKind::Movie,
+                number: 452654,
+            }
+        );
+
+        for id in ["movie-452654", "serial-45534"] {
+            assert_eq!(id.parse::<ReleaseId>().unwrap().to_string(), id);
+        }
+
+        for id in [
+            "",
+            "movie",
+            "movie-",
+            "anime-1",
+            "serial-+1",
+            "serial-12a",
+            "serial-1-2",
+        ] {
+            assert!(
+                matches!(id.parse::<ReleaseId>(), Err(Error::InvalidParameter(_))),
+                "{id}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_link_with_quality() {
+        let mut release = get_default_kodik_release();
+
+        release.link = "//kodik.info/serial/45534/d8619e900d12/720p".to_owned();
+
+        assert_eq!(
+            release.link_with_quality(LinkQuality::P1080),
+            "//kodik.info/serial/45534/d8619e900d12/1080p"
+        );
+        assert_eq!(
+            LinkQuality::P480.apply("//kodik.info/serial/45534/d8619e900d12"),
+            "//kodik.info/serial/45534/d8619e900d12"
+        );
+        assert_eq!(LinkQuality::P480.apply("720p"), "720p");
+        assert_eq!(
+            LinkQuality::P360.apply("//kodik.info/serial/45534/d8619e900d12/p"),
+            "//kodik.info/serial/45534/d8619e900d12/p"
+        );
+    }
+
+    #[test]
+    fn test_absolute_link() {
+        let mut release = get_default_kodik_release();
+
+        for (link, absolute) in [
+            (
+                "//kodik.info/serial/45534/d8619e900d12/720p",
+                "https://kodik.info/serial/45534/d8619e900d12/720p",
+            ),
+            (
+                "http://kodik.cc/seria/119611/09249413a7eb3c03b15df57cd56a051b/720p",
+                "http://kodik.cc/seria/119611/09249413a7eb3c03b15df57cd56a051b/720p",
+            ),
+            (
+                "https://kodik.info/serial/45534/d8619e900d12/720p",
+                "https://kodik.info/serial/45534/d8619e900d12/720p",
+            ),
+        ] {
+            release.link = link.to_owned();
+
+            assert_eq!(release.absolute_link(), absolute);
+        }
+    }
+
+    #[test]
+    fn test_other_enum_values() {
+        let quality: ReleaseQuality = serde_json::from_value(json!("WEB-DLRip 2160p")).unwrap();
+
+        assert_eq!(quality, ReleaseQuality::Other("WEB-DLRip 2160p".to_owned()));
+        assert_eq!(quality.as_wire_str(), "WEB-DLRip 2160p");
+        assert_eq!(
+            serde_json::to_value(&quality).unwrap(),
+            json!("WEB-DLRip 2160p")
+        );
+
+        let quality: ReleaseQuality = serde_json::from_value(json!("WEB-DLRip 720p")).unwrap();
+
+        assert_eq!(quality, ReleaseQuality::WebDlRip720p);
+        assert_eq!(quality.as_wire_str(), "WEB-DLRip 720p");
+
+        let anime_kind: AnimeKind = serde_json::from_value(json!("tv_72")).unwrap();
+
+        assert_eq!(anime_kind, AnimeKind::Other("tv_72".to_owned()));
+        assert_eq!(anime_kind.as_wire_str(), "tv_72");
+        assert_eq!(serde_json::to_value(&anime_kind).unwrap(), json!("tv_72"));
+
+        for anime_kind in [
+            AnimeKind::Tv,
+            AnimeKind::Movie,
+            AnimeKind::Ova,
+            AnimeKind::Ona,
+            AnimeKind::Special,
+            AnimeKind::Music,
+            AnimeKind::Tv13,
+            AnimeKind::Tv24,
+            AnimeKind::Tv48,
+        ] {
+            assert_eq!(
+                serde_json::to_value(&anime_kind).unwrap(),
+                json!(anime_kind.as_wire_str())
+            );
+            assert_eq!(
+                serde_json::from_value::<AnimeKind>(json!(anime_kind.as_wire_str())).unwrap(),
+                anime_kind
+            );
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        assert_eq!(Range::Exact(7.0).to_string(), "7");
+        assert_eq!(Range::Exact(7.5).to_string(), "7.5");
+        assert_eq!(Range::Between(6.5, 8.0).to_string(), "6.5-8");
+        assert_eq!(Range::AtLeast(7.3).to_string(), "7.3-10");
+        assert_eq!(Range::AtMost(5.5).to_string(), "0-5.5");
+
+        assert_eq!(DurationRange::Exact(24).to_string(), "24");
+        assert_eq!(DurationRange::Between(20, 30).to_string(), "20-30");
+        assert_eq!(DurationRange::AtLeast(90).to_string(), "90-65535");
+        assert_eq!(DurationRange::AtMost(30).to_string(), "0-30");
+    }
+
+    #[test]
+    fn test_release_eq() {
+        let mut release = get_default_kodik_release();
+
+        release.material_data = Some(MaterialData {
+            kinopoisk_rating: Some(8.1),
+            ..MaterialData::default()
+        });
+
+        assert_eq!(release, release.clone());
+
+        let mut other_release = release.clone();
+
+        other_release.material_data = Some(MaterialData {
+            kinopoisk_rating: Some(8.2),
+            ..MaterialData::default()
+        });
+
+        assert_ne!(release, other_release);
+
+        let mut other_release = release.clone();
+
+        other_release.translation.id += 1;
+
+        assert_ne!(release, other_release);
+    }
+
+    #[test]
+    fn test_blocked_seasons() {
+        let mut value = serde_json::to_value(get_default_kodik_release()).unwrap();
+
+        value["blocked_seasons"] = json!("all");
+
+        let release: Release = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(release.blocked_seasons, BlockedSeasons::AllBlocked);
+        assert_eq!(
+            release.blocked_seasons.season("1"),
+            Some(&BlockedSeason::All)
+        );
+        assert!(release.is_fully_blocked());
+
+        value["blocked_seasons"] = json!({});
+
+        let release: Release = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(
+            release.blocked_seasons,
+            BlockedSeasons::PerSeason(BTreeMap::new())
+        );
+        assert!(!release.is_fully_blocked());
+
+        value["blocked_seasons"] = json!({ "1": "all", "2": ["3", "4"] });
+
+        let release: Release = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(
+            release.blocked_seasons,
+            BlockedSeasons::PerSeason(BTreeMap::from([
+                ("1".to_owned(), BlockedSeason::All),
+                (
+                    "2".to_owned(),
+                    BlockedSeason::Episodes(vec!["3".to_owned(), "4".to_owned()])
+                ),
+            ]))
+        );
+        assert_eq!(release.blocked_seasons.season("3"), None);
+        assert!(!release.is_fully_blocked());
+        assert_eq!(
+            serde_json::to_value(&release).unwrap()["blocked_seasons"],
+            value["blocked_seasons"]
+        );
+
+        value.as_object_mut().unwrap().remove("blocked_seasons");
+
+        let release: Release = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(release.blocked_seasons, BlockedSeasons::None);
+
+        value["blocked_seasons"] = json!("some");
+
+        assert!(serde_json::from_value::<Release>(value).is_err());
+    }
+
+    #[test]
+    fn test_without_screenshots() {
+        let mut value = serde_json::to_value(get_default_kodik_release()).unwrap();
+
+        value["screenshots"] = json!([]);
+
+        let release: Release = serde_json::from_value(value.clone()).unwrap();
+
+        assert!(release.screenshots.is_empty());
+
+        value.as_object_mut().unwrap().remove("screenshots");
+
+        let release: Release = serde_json::from_value(value).unwrap();
+
+        assert!(release.screenshots.is_empty());
+
+        let episode: Episode = serde_json::from_value(json!({
+            "title": null,
+            "link": "//kodik.info/seria/1049812/a3f6fc2d1a3b/720p",
+        }))
+        .unwrap();
+
+        assert!(episode.screenshots.is_empty());
+    }
+
+    #[test]
+    fn test_episode_union() {
+        let link = "//kodik.info/seria/1049812/a3f6fc2d1a3b/720p";
+
+        let episode: EpisodeUnion = serde_json::from_value(json!(link)).unwrap();
+
+        assert!(matches!(episode, EpisodeUnion::Link(episode_link) if episode_link == link));
+
+        let episode: EpisodeUnion = serde_json::from_value(json!({
+            "title": null,
+            "link": link,
+            "screenshots": ["https://i.kodik.biz/screenshots/seria/104981222/1.jpg"],
+        }))
+        .unwrap();
+
+        assert!(matches!(
+            episode,
+            EpisodeUnion::Episode(Episode { title: None, link: episode_link, screenshots })
+                if episode_link == link && screenshots.len() == 1
+        ));
+
+        let episode: EpisodeUnion = serde_json::from_value(json!({
+            "title": "Спешл",
+            "link": link,
+            "screenshots": [],
+            "unknown": 1,
+        }))
+        .unwrap();
+
+        assert!(matches!(
+            episode,
+            EpisodeUnion::Episode(Episode { title: Some(title), .. }) if title == "Спешл"
+        ));
+
+        let error = serde_json::from_value::<EpisodeUnion>(json!({
+            "title": "Спешл",
+            "screenshots": [],
+        }))
+        .unwrap_err();
+
+        assert!(error.to_string().contains("missing field `link`"));
+
+        for value in [json!(null), json!(1), json!([link])] {
+            let error = serde_json::from_value::<EpisodeUnion>(value).unwrap_err();
+
+            assert!(error
+                .to_string()
+                .contains("expected an episode link or an episode object"));
+        }
+
+        let season: Season = serde_json::from_value(json!({
+            "link": "//kodik.info/season/45534/d8619e900d122ea8eff8b55891b09bac/720p",
+            "episodes": {
+                "1": link,
+                "2": { "title": null, "link": link, "screenshots": [] },
+            },
+        }))
+        .unwrap();
+
+        assert!(matches!(
+            season.episodes[&NumericKey::from(1)],
+            EpisodeUnion::Link(_)
+        ));
+        assert!(matches!(
+            season.episodes[&NumericKey::from(2)],
+            EpisodeUnion::Episode(_)
+        ));
+    }
+
+    #[test]
+    fn test_numeric_key_order() {
+        let link = "//kodik.info/seria/1049812/a3f6fc2d1a3b/720p";
+
+        let season: Season = serde_json::from_value(json!({
+            "link": "//kodik.info/season/45534/d8619e900d122ea8eff8b55891b09bac/720p",
+            "episodes": { "10": link, "special": link, "2": link, "1": link },
+        }))
+        .unwrap();
+
+        assert_eq!(
+            season
+                .episodes
+                .keys()
+                .map(NumericKey::as_str)
+                .collect::<Vec<_>>(),
+            ["1", "2", "10", "special"]
+        );
+
+        // Serialized back with the same keys
+        assert_eq!(
+            serde_json::to_value(&season.episodes).unwrap(),
+            json!({ "1": link, "2": link, "10": link, "special": link })
+        );
+    }
+
+    #[test]
+    fn test_is_complete() {
+        let mut kodik_release = get_default_kodik_release();
+
+        assert_eq!(kodik_release.is_complete(), None);
+
+        let get_season = |episodes_count: i32| Season {
+            title: None,
+            link: "//kodik.info/season/45534/d8619e900d122ea8eff8b55891b09bac/720p".to_owned(),
+            episodes: (1..=episodes_count)
+                .map(|episode| {
+                    let link = format!("//kodik.info/seria/1049812{episode:02}/a3f6fc2d1a3b/720p");
+
+                    (episode.to_string().into(), EpisodeUnion::Link(link))
+                })
+                .collect(),
+        };
+
+        kodik_release.seasons = Some(BTreeMap::from([("1".into(), get_season(0))]));
+
+        assert_eq!(kodik_release.is_complete(), None);
+
+        kodik_release.seasons = Some(BTreeMap::from([("1".into(), get_season(7))]));
+
+        assert_eq!(kodik_release.is_complete(), Some(false));
+
+        kodik_release.seasons = Some(BTreeMap::from([("1".into(), get_season(10))]));
+
+        assert_eq!(kodik_release.is_complete(), Some(true));
+
+        kodik_release.episodes_count = None;
+
+        assert_eq!(kodik_release.is_complete(), Some(true));
+
+        kodik_release.last_episode = Some(12);
+
+        assert_eq!(kodik_release.is_complete(), Some(false));
+
+        kodik_release.last_episode = None;
+
+        assert_eq!(kodik_release.is_complete(), None);
+    }
+
+    #[test]
+    fn test_season_and_episode_count() {
+        let mut kodik_release = get_default_kodik_release();
+
+        kodik_release.material_data = Some(MaterialData {
+            episodes_total: Some(12),
+            ..Default::default()
+        });
+
+        assert_eq!(kodik_release.season_count(), Some(1));
+        assert_eq!(kodik_release.total_episode_count(), Some(10));
+
+        let get_season = |episodes_count: i32| Season {
+            title: None,
+            link: "//kodik.info/season/45534/d8619e900d122ea8eff8b55891b09bac/720p".to_owned(),
+            episodes: (1..=episodes_count)
+                .map(|episode| {
+                    let link = format!("//kodik.info/seria/1049812{episode:02}/a3f6fc2d1a3b/720p");
+
+                    (episode.to_string().into(), EpisodeUnion::Link(link))
+                })
+                .collect(),
+        };
+
+        kodik_release.seasons = Some(BTreeMap::from([
+            ("1".into(), get_season(8)),
+            ("2".into(), get_season(3)),
+        ]));
+
+        assert_eq!(kodik_release.season_count(), Some(2));
+        assert_eq!(kodik_release.total_episode_count(), Some(11));
+
+        kodik_release.seasons = Some(BTreeMap::new());
+        kodik_release.episodes_count = None;
+
+        assert_eq!(kodik_release.season_count(), Some(1));
+        assert_eq!(kodik_release.total_episode_count(), Some(12));
+
+        kodik_release.last_season = None;
+        kodik_release.material_data = None;
+
+        assert_eq!(kodik_release.season_count(), None);
+        assert_eq!(kodik_release.total_episode_count(), None);
+    }
+
+    #[test]
+    fn test_episode_progress() {
+        let mut kodik_release = get_default_kodik_release();
+
+        kodik_release.last_episode = Some(8);
+        kodik_release.episodes_count = Some(10);
+        kodik_release.material_data = Some(MaterialData {
+            episodes_aired: Some(12),
+            episodes_total: Some(24),
+            ..Default::default()
+        });
+
+        assert_eq!(kodik_release.episode_progress(), Some((12, 24)));
+
+        kodik_release.material_data = Some(MaterialData {
+            episodes_total: Some(0),
+            ..Default::default()
+        });
+
+        assert_eq!(kodik_release.episode_progress(), Some((8, 10)));
+
+        kodik_release.material_data = None;
+        kodik_release.last_episode = None;
+
+        assert_eq!(kodik_release.episode_progress(), Some((10, 10)));
+
+        kodik_release.episodes_count = None;
+
+        assert_eq!(kodik_release.episode_progress(), None);
+    }
+
+    #[test]
+    fn test_external_ids_normalization() {
+        let mut kodik_release = get_default_kodik_release();
+
+        kodik_release.kinopoisk_id = Some(" ".to_owned());
+        kodik_release.imdb_id = Some("12590266".to_owned());
+        kodik_release.worldart_link =
+            Some("http://www.world-art.ru/cinema/cinema.php?id=2417".to_owned());
+
+        let external_ids = kodik_release.external_ids();
+
+        assert_eq!(external_ids.kinopoisk, None);
+        assert_eq!(external_ids.imdb, Some("tt12590266".to_owned()));
+        assert_eq!(
+            external_ids.worldart,
+            Some(WorldartId {
+                section: "cinema".to_owned(),
+                id: 2417,
+            })
+        );
+
+        kodik_release.imdb_id = Some("TT12590266".to_owned());
+        kodik_release.worldart_link = Some("not a link".to_owned());
+
+        let external_ids = kodik_release.external_ids();
+
+        assert_eq!(external_ids.imdb, Some("tt12590266".to_owned()));
+        assert_eq!(external_ids.worldart, None);
+    }
+
+    #[test]
+    fn test_processing_time() {
+        assert_eq!(
+            parse_processing_time("15ms"),
+            Some(Duration::from_millis(15))
+        );
+        assert_eq!(
+            parse_processing_time("1.2s"),
+            Some(Duration::from_millis(1200))
+        );
+        assert_eq!(parse_processing_time("0ms"), Some(Duration::ZERO));
+        assert_eq!(parse_processing_time("15 minutes"), None);
+        assert_eq!(parse_processing_time("ms"), None);
+        assert_eq!(parse_processing_time("-3ms"), None);
+        assert_eq!(parse_processing_time(""), None);
+    }
 }
@@ -2,7 +2,11 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::types::{EpisodeUnion, Release};
+use crate::{
+    list::ListResponse,
+    search::SearchResponse,
+    types::{absolute_link, BlockedSeason, EpisodeUnion, LinkQuality, NumericKey, Release},
+};
 
 /// Represents a release unified episode object on Kodik
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -16,6 +20,18 @@ pub struct UnifiedEpisode {
     pub screenshots: Vec<String>,
 }
 
+impl UnifiedEpisode {
+    /// Returns the link with the `https:` scheme if it is protocol-relative, see [`absolute_link`]
+    pub fn absolute_link(&self) -> String {
+        absolute_link(&self.link)
+    }
+
+    /// Returns the link with the quality segment replaced, see [`LinkQuality`]
+    pub fn link_with_quality(&self, quality: LinkQuality) -> String {
+        quality.apply(&self.link)
+    }
+}
+
 /// Represents a release unified season object on Kodik
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct UnifiedSeason {
@@ -24,21 +40,45 @@ pub struct UnifiedSeason {
 
     pub link: String,
 
-    pub episodes: BTreeMap<String, UnifiedEpisode>,
+    pub episodes: BTreeMap<NumericKey, UnifiedEpisode>,
+}
+
+/// A playable link of an episode with its season and episode numbers, see [`Release::episode_links`]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct EpisodeLink {
+    pub season: String,
+
+    pub episode: String,
+
+    /// For example, it сan be marked as special
+    pub title: Option<String>,
+
+    pub link: String,
+}
+
+/// Represents a release unified season object with episodes sorted by [`NumericKey`]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct SortedUnifiedSeason {
+    /// For example, it can be marked as a recap, special, etc.
+    pub title: Option<String>,
+
+    pub link: String,
+
+    pub episodes: Vec<(NumericKey, UnifiedEpisode)>,
 }
 
 /// Returns seasons and episodes in a unified format for the Kodik release.
 ///
 /// Kodik returns different response formats for movies, shows, depending on the parameters and the state of the sun.
-pub fn unify_seasons(release: &Release) -> BTreeMap<String, UnifiedSeason> {
+pub fn unify_seasons(release: &Release) -> BTreeMap<NumericKey, UnifiedSeason> {
     let Some(kodik_seasons) = &release.seasons else {
         return BTreeMap::from([(
-            "1".to_owned(),
+            NumericKey::from(1),
             UnifiedSeason {
                 title: None,
                 link: release.link.clone(),
                 episodes: BTreeMap::from([(
-                    "1".to_owned(),
+                    NumericKey::from(1),
                     UnifiedEpisode {
                         title: None,
                         link: release.link.clone(),
@@ -84,10 +124,79 @@ pub fn unify_seasons(release: &Release) -> BTreeMap<String, UnifiedSeason> {
     seasons
 }
 
+/// Same as [`unify_seasons`] for every release of the list response, paired with the ID of the release
+///
+/// The releases keep the order of the response.
+pub fn unify_response_seasons(
+    response: &ListResponse,
+) -> Vec<(String, BTreeMap<NumericKey, UnifiedSeason>)> {
+    unify_releases_seasons(&response.results)
+}
+
+/// Same as [`unify_response_seasons`], for the search response
+pub fn unify_search_response_seasons(
+    response: &SearchResponse,
+) -> Vec<(String, BTreeMap<NumericKey, UnifiedSeason>)> {
+    unify_releases_seasons(&response.results)
+}
+
+fn unify_releases_seasons(
+    releases: &[Release],
+) -> Vec<(String, BTreeMap<NumericKey, UnifiedSeason>)> {
+    releases
+        .iter()
+        .map(|release| (release.id.clone(), unify_seasons(release)))
+        .collect()
+}
+
+/// Same as [`unify_seasons`], but without the seasons and episodes listed in [`Release::blocked_seasons`]
+///
+/// [`crate::types::BlockedSeasons::AllBlocked`] and [`BlockedSeason::All`] drop the whole season, [`BlockedSeason::Episodes`] drops the listed episodes. A season left without episodes is dropped as well.
+pub fn unify_seasons_filtered(release: &Release) -> BTreeMap<NumericKey, UnifiedSeason> {
+    let mut seasons = unify_seasons(release);
+
+    seasons.retain(|season_num, season| {
+        match release.blocked_seasons.season(season_num.as_str()) {
+            None => true,
+            Some(BlockedSeason::All) => false,
+            Some(BlockedSeason::Episodes(blocked_episodes)) => {
+                season.episodes.retain(|episode_num, _| {
+                    !blocked_episodes
+                        .iter()
+                        .any(|blocked| episode_num == blocked.as_str())
+                });
+
+                !season.episodes.is_empty()
+            }
+        }
+    });
+
+    seasons
+}
+
+/// Same as [`unify_seasons`], but seasons and episodes are collected into vectors in the order of their [`NumericKey`], so episode `10` goes after `2`
+///
+/// Keys that are not numbers go after the numeric ones.
+pub fn unify_seasons_sorted(release: &Release) -> Vec<(NumericKey, SortedUnifiedSeason)> {
+    unify_seasons(release)
+        .into_iter()
+        .map(|(season_num, season)| {
+            (
+                season_num,
+                SortedUnifiedSeason {
+                    title: season.title,
+                    link: season.link,
+                    episodes: season.episodes.into_iter().collect(),
+                },
+            )
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::types::{
-        Episode, ReleaseQuality, ReleaseType, Season, Translation, TranslationType,
+        BlockedSeasons, Episode, ReleaseQuality, ReleaseType, Season, Translation, TranslationType,
     };
 
     use super::*;
@@ -118,7 +227,7 @@ mod tests {
             },
             created_at: "2022-09-14T10:54:34Z".to_owned(),
             updated_at: "2022-09-23T22:31:33Z".to_owned(),
-            blocked_seasons: Some(BTreeMap::new()),
+            blocked_seasons: BlockedSeasons::PerSeason(BTreeMap::new()),
             seasons: None,
             last_season: Some(1),
             last_episode: Some(10),
@@ -138,12 +247,12 @@ mod tests {
         assert_eq!(
             unified_season,
             BTreeMap::from([(
-                "1".to_owned(),
+                "1".into(),
                 UnifiedSeason {
                     title: None,
                     link: kodik_release.link.clone(),
                     episodes: BTreeMap::from([(
-                        "1".to_owned(),
+                        "1".into(),
                         UnifiedEpisode {
                             title: None,
                             link: kodik_release.link.clone(),
@@ -155,25 +264,24 @@ mod tests {
         )
     }
 
-    #[test]
-    fn test_unify_kodik_with_seasons() {
+    fn get_kodik_release_with_seasons() -> Release {
         let mut kodik_release = get_default_kodik_release();
 
         let seasons = BTreeMap::from([(
-            "1".to_owned(),
+            "1".into(),
             Season {
                 link: kodik_release.link.clone(),
                 title: None,
                 episodes: BTreeMap::from([
                     (
-                        "1".to_owned(),
+                        "1".into(),
                         EpisodeUnion::Link(
                             "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p/1"
                                 .to_owned(),
                         ),
                     ),
                     (
-                        "2".to_owned(),
+                        "2".into(),
                         EpisodeUnion::Episode(Episode {
                             title: None,
                             link:
@@ -183,7 +291,7 @@ mod tests {
                         }),
                     ),
                     (
-                        "3".to_owned(),
+                        "3".into(),
                         EpisodeUnion::Link(
                             "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p/3"
                                 .to_owned(),
@@ -195,24 +303,31 @@ mod tests {
 
         kodik_release.seasons = Some(seasons);
 
+        kodik_release
+    }
+
+    #[test]
+    fn test_unify_kodik_with_seasons() {
+        let kodik_release = get_kodik_release_with_seasons();
+
         let unified_season = unify_seasons(&kodik_release);
 
         assert_eq!(unified_season, BTreeMap::from([
-            ("1".to_owned(), UnifiedSeason {
+            ("1".into(), UnifiedSeason {
                 title: None,
                 link: kodik_release.link.clone(),
                 episodes: BTreeMap::from([
-                    ("1".to_owned(), UnifiedEpisode {
+                    ("1".into(), UnifiedEpisode {
                         title: None,
                         link: "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p/1".to_owned(),
                         screenshots: kodik_release.screenshots.clone(),
                     }),
-                    ("2".to_owned(), UnifiedEpisode {
+                    ("2".into(), UnifiedEpisode {
                         title: None,
                         link: "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p/2".to_owned(),
                         screenshots: kodik_release.screenshots.clone(),
                     }),
-                    ("3".to_owned(), UnifiedEpisode {
+                    ("3".into(), UnifiedEpisode {
                         title: None,
                         link: "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p/3".to_owned(),
                         screenshots: kodik_release.screenshots,
@@ -221,4 +336,204 @@ mod tests {
             })
         ]))
     }
+
+    #[test]
+    fn test_unify_response_seasons() {
+        let mut movie = get_default_kodik_release();
+
+        movie.id = "movie-94311".to_owned();
+        movie.release_type = ReleaseType::Anime;
+        movie.link = "//kodik.info/video/94311/0e3be2c4ab8c1c9c6e5cb9ce5e09a7e0/720p".to_owned();
+
+        let serial = get_kodik_release_with_seasons();
+
+        let response = ListResponse {
+            time: "3ms".to_owned(),
+            total: 2,
+            prev_page: None,
+            next_page: None,
+            results: vec![movie.clone(), serial.clone()],
+        };
+
+        let expected = vec![
+            (movie.id.clone(), unify_seasons(&movie)),
+            (serial.id.clone(), unify_seasons(&serial)),
+        ];
+
+        let unified_seasons = unify_response_seasons(&response);
+
+        assert_eq!(unified_seasons, expected);
+        assert_eq!(unified_seasons[0].1[&NumericKey::from(1)].link, movie.link);
+        assert_eq!(unified_seasons[1].1[&NumericKey::from(1)].episodes.len(), 3);
+
+        let response = SearchResponse {
+            time: response.time,
+            total: response.total,
+            prev_page: None,
+            next_page: None,
+            results: response.results,
+        };
+
+        assert_eq!(unify_search_response_seasons(&response), expected);
+    }
+
+    #[test]
+    fn test_episode_links() {
+        let kodik_release = get_kodik_release_with_seasons();
+
+        assert_eq!(
+            kodik_release.episode_links().collect::<Vec<_>>(),
+            (1..=3)
+                .map(|episode| EpisodeLink {
+                    season: "1".to_owned(),
+                    episode: episode.to_string(),
+                    title: None,
+                    link: format!(
+                        "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p/{episode}"
+                    ),
+                })
+                .collect::<Vec<_>>()
+        );
+
+        let kodik_release = get_default_kodik_release();
+
+        assert_eq!(
+            kodik_release.episode_links().collect::<Vec<_>>(),
+            [EpisodeLink {
+                season: "1".to_owned(),
+                episode: "1".to_owned(),
+                title: None,
+                link: kodik_release.link.clone(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unify_kodik_sorted() {
+        let mut kodik_release = get_default_kodik_release();
+
+        let season = |episodes: &[&str]| {
+            Season {
+            link: kodik_release.link.clone(),
+            title: None,
+            episodes: episodes
+                .iter()
+                .map(|episode_num| {
+                    (
+                        (*episode_num).into(),
+                        EpisodeUnion::Link(format!(
+                            "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p/{episode_num}"
+                        )),
+                    )
+                })
+                .collect(),
+        }
+        };
+
+        let seasons = BTreeMap::from([
+            ("1".into(), season(&["1", "2", "10", "special"])),
+            ("10".into(), season(&["1"])),
+            ("2".into(), season(&["1"])),
+            ("recap".into(), season(&["1"])),
+        ]);
+
+        kodik_release.seasons = Some(seasons);
+
+        let unified_seasons = unify_seasons_sorted(&kodik_release);
+
+        assert_eq!(
+            unified_seasons
+                .iter()
+                .map(|(season_num, _)| season_num.as_str())
+                .collect::<Vec<_>>(),
+            ["1", "2", "10", "recap"]
+        );
+
+        let (_, first_season) = &unified_seasons[0];
+
+        assert_eq!(
+            first_season
+                .episodes
+                .iter()
+                .map(|(episode_num, _)| episode_num.as_str())
+                .collect::<Vec<_>>(),
+            ["1", "2", "10", "special"]
+        );
+        assert_eq!(
+            first_season.episodes[2].1.link,
+            "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p/10"
+        );
+    }
+
+    #[test]
+    fn test_unify_kodik_filtered_without_seasons() {
+        let mut kodik_release = get_default_kodik_release();
+
+        assert_eq!(
+            unify_seasons_filtered(&kodik_release),
+            unify_seasons(&kodik_release)
+        );
+
+        kodik_release.blocked_seasons =
+            BlockedSeasons::PerSeason(BTreeMap::from([("1".into(), BlockedSeason::All)]));
+
+        assert!(unify_seasons_filtered(&kodik_release).is_empty());
+    }
+
+    #[test]
+    fn test_unify_kodik_filtered_with_seasons() {
+        let mut kodik_release = get_default_kodik_release();
+
+        let season = |episodes: &[&str]| {
+            Season {
+            link: kodik_release.link.clone(),
+            title: None,
+            episodes: episodes
+                .iter()
+                .map(|episode_num| {
+                    (
+                        (*episode_num).into(),
+                        EpisodeUnion::Link(format!(
+                            "//kodik.info/serial/45534/d8619e900d122ea8eff8b55891b09bac/720p/{episode_num}"
+                        )),
+                    )
+                })
+                .collect(),
+        }
+        };
+
+        let seasons = BTreeMap::from([
+            ("1".into(), season(&["1", "2", "3"])),
+            ("2".into(), season(&["1", "2"])),
+            ("3".into(), season(&["1"])),
+            ("4".into(), season(&["1"])),
+        ]);
+
+        kodik_release.seasons = Some(seasons);
+        kodik_release.blocked_seasons = BlockedSeasons::PerSeason(BTreeMap::from([
+            ("1".into(), BlockedSeason::Episodes(vec!["2".to_owned()])),
+            ("2".into(), BlockedSeason::All),
+            ("3".into(), BlockedSeason::Episodes(vec!["1".to_owned()])),
+        ]));
+
+        let unified_seasons = unify_seasons_filtered(&kodik_release);
+
+        assert_eq!(
+            unified_seasons
+                .iter()
+                .map(|(season_num, season)| (
+                    season_num.as_str(),
+                    season
+                        .episodes
+                        .keys()
+                        .map(NumericKey::as_str)
+                        .collect::<Vec<_>>()
+                ))
+                .collect::<Vec<_>>(),
+            [("1", vec!["1", "3"]), ("4", vec!["1"])]
+        );
+
+        // The plain version keeps everything
+        assert_eq!(unify_seasons(&kodik_release).len(), 4);
+    }
 }
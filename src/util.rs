@@ -1,7 +1,17 @@
-use serde::ser;
+use std::{borrow::Cow, collections::HashSet};
+
+use serde::{
+    de::{self, Visitor},
+    ser, Deserializer,
+};
 
 use crate::error::Error;
 
+/// Response of the Kodik API which contains either a result or an error
+pub(crate) trait ResponseUnion {
+    fn is_error(&self) -> bool;
+}
+
 pub fn serialize_into_query_parts<T: ser::Serialize>(
     input: T,
 ) -> Result<Vec<(String, String)>, Error> {
@@ -13,3 +23,109 @@ pub fn serialize_into_query_parts<T: ser::Serialize>(
 
     Ok(parts)
 }
+
+/// Rejects list values containing a comma
+///
+/// Lists are sent joined with commas, so the API would split such a value into several ones and there is no escaping for it. Checked once before a query is executed, not on every serialization.
+pub(crate) fn reject_commas_in_lists<T: ser::Serialize>(input: &T) -> Result<(), Error> {
+    let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(input) else {
+        return Ok(());
+    };
+
+    for (key, value) in fields {
+        let serde_json::Value::Array(values) = value else {
+            continue;
+        };
+
+        let value = values
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .find(|value| value.contains(','));
+
+        if let Some(value) = value {
+            return Err(Error::InvalidParameter(format!(
+                "{key} value {value:?} contains a comma, which the API would split into several values"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the parts that are missing from the query of the url
+///
+/// Pagination links don't necessarily preserve every parameter of the original request, so the missing ones are re-sent along with them.
+pub(crate) fn missing_query_parts<'a>(
+    url: &str,
+    parts: &'a [(String, String)],
+) -> Vec<&'a (String, String)> {
+    let Ok(url) = url::Url::parse(url) else {
+        return parts.iter().collect();
+    };
+
+    let keys: HashSet<String> = url.query_pairs().map(|(key, _)| key.into_owned()).collect();
+
+    parts
+        .iter()
+        .filter(|(key, _)| !keys.contains(key))
+        .collect()
+}
+
+pub(crate) fn into_owned_str(value: Option<Cow<'_, str>>) -> Option<Cow<'static, str>> {
+    value.map(|value| Cow::Owned(value.into_owned()))
+}
+
+pub(crate) fn into_owned_list<T: Clone + 'static>(
+    value: Option<Cow<'_, [T]>>,
+) -> Option<Cow<'static, [T]>> {
+    value.map(|value| Cow::Owned(value.into_owned()))
+}
+
+pub(crate) fn into_owned_str_list(
+    value: Option<Cow<'_, [Cow<'_, str>]>>,
+) -> Option<Cow<'static, [Cow<'static, str>]>> {
+    value.map(|value| {
+        value
+            .iter()
+            .map(|value| Cow::Owned(value.clone().into_owned()))
+            .collect()
+    })
+}
+
+/// Turns a typed filter value, such as [`crate::types::Range`], into a single-value query list
+pub(crate) fn range_query_list<'a>(range: impl ToString) -> Cow<'a, [Cow<'a, str>]> {
+    Cow::Owned(vec![Cow::Owned(range.to_string())])
+}
+
+/// Deserializes a number which the API sometimes sends as a numeric string, for example `"12"`
+pub(crate) fn deserialize_number_or_string<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<i32, D::Error> {
+    struct NumberOrStringVisitor;
+
+    impl<'de> Visitor<'de> for NumberOrStringVisitor {
+        type Value = i32;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a number or a numeric string")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+            i32::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            i32::try_from(value)
+                .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+
+    deserializer.deserialize_any(NumberOrStringVisitor)
+}